@@ -4,10 +4,17 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use linked_hash_map::LinkedHashMap;
+use lru::LruCache;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use slotmap::{DefaultKey, SlotMap};
 use std::collections::vec_deque::VecDeque;
-use std::collections::LinkedList;
+use std::collections::{HashMap, LinkedList};
+use std::num::NonZeroUsize;
 use index_list::{IndexList, ListIndex};
 
+const LRU_CAPACITY: usize = 1024;
+
 fn indexlist_head(n: u32) {
     let mut list = IndexList::<u32>::new();
     (1..=n).rev().for_each(|i| { list.insert_first(i); });
@@ -113,6 +120,124 @@ fn linkedlist_iter(n: u32) {
     assert_eq!(accum, 0);
 }
 
+// keys with a skewed distribution, where a few keys are hot and the rest are
+// spread over a key space four times the cache capacity
+fn lru_keys(n: u32) -> Vec<u32> {
+    let mut rng = StdRng::seed_from_u64(42);
+    let space = 4 * LRU_CAPACITY as u32;
+    (0..n).map(|_| {
+        if rng.gen_bool(0.5) {
+            rng.gen_range(0..space / 16)
+        } else {
+            rng.gen_range(0..space)
+        }
+    }).collect()
+}
+
+fn indexlist_lru(keys: &[u32]) -> usize {
+    let mut list = IndexList::<u32>::with_capacity(LRU_CAPACITY);
+    let mut map = HashMap::<u32, ListIndex>::with_capacity(LRU_CAPACITY);
+    let mut hits = 0;
+    for &key in keys {
+        if let Some(&index) = map.get(&key) {
+            list.touch(index);
+            hits += 1;
+            continue;
+        }
+        if list.len() == LRU_CAPACITY {
+            let old = list.remove_first().unwrap();
+            map.remove(&old);
+        }
+        map.insert(key, list.insert_last(key));
+    }
+    hits
+}
+
+fn lru_lru(keys: &[u32]) -> usize {
+    let mut cache = LruCache::<u32, u32>::new(NonZeroUsize::new(LRU_CAPACITY).unwrap());
+    let mut hits = 0;
+    for &key in keys {
+        if cache.get(&key).is_some() {
+            hits += 1;
+        } else {
+            cache.put(key, key);
+        }
+    }
+    hits
+}
+
+fn linkedhashmap_lru(keys: &[u32]) -> usize {
+    let mut cache = LinkedHashMap::<u32, u32>::with_capacity(LRU_CAPACITY);
+    let mut hits = 0;
+    for &key in keys {
+        if cache.get_refresh(&key).is_some() {
+            hits += 1;
+            continue;
+        }
+        if cache.len() == LRU_CAPACITY {
+            cache.pop_front();
+        }
+        cache.insert(key, key);
+    }
+    hits
+}
+
+// random inserts and removes in the body of the list, which leaves the free
+// slots scattered over the vector
+fn indexlist_churn(n: u32) {
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut list = IndexList::<u32>::new();
+    let mut live: Vec<ListIndex> = Vec::new();
+    (0..n).for_each(|i| live.push(list.insert_last(i)));
+    for i in 0..n {
+        let pos = rng.gen_range(0..live.len());
+        if rng.gen_bool(0.5) {
+            list.remove(live.swap_remove(pos));
+        } else {
+            live.push(list.insert_before(live[pos], i));
+        }
+    }
+    let accum: u64 = list.iter().map(|&i| i as u64).sum();
+    black_box(accum);
+}
+
+fn linkedlist_churn(n: u32) {
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut list = LinkedList::<u32>::new();
+    (0..n).for_each(|i| list.push_back(i));
+    for i in 0..n {
+        let pos = rng.gen_range(0..list.len());
+        let mut tail = list.split_off(pos);
+        if rng.gen_bool(0.5) {
+            tail.pop_front();
+        } else {
+            tail.push_front(i);
+        }
+        list.append(&mut tail);
+    }
+    let accum: u64 = list.iter().map(|&i| i as u64).sum();
+    black_box(accum);
+}
+
+fn handles(n: u32) -> Vec<usize> {
+    let mut rng = StdRng::seed_from_u64(11);
+    (0..n).map(|_| rng.gen_range(0..n as usize)).collect()
+}
+
+fn indexlist_handles(n: u32, picks: &[usize]) {
+    let mut list = IndexList::<u32>::new();
+    let keys: Vec<ListIndex> = (0..n).map(|i| list.insert_last(i)).collect();
+    let accum: u64 = picks.iter().map(|&p| *list.get(keys[p]).unwrap() as u64).sum();
+    black_box(accum);
+}
+
+fn slotmap_handles(n: u32, picks: &[usize]) {
+    let mut map = SlotMap::<DefaultKey, u32>::new();
+    let keys: Vec<DefaultKey> = (0..n).map(|i| map.insert(i)).collect();
+    let accum: u64 = picks.iter().map(|&p| *map.get(keys[p]).unwrap() as u64).sum();
+    black_box(accum);
+}
+
 fn criterion_benchmark(c: &mut Criterion) {
     let count = 10 * 1024;
     c.bench_function("indexlist-head", |b| b.iter(||
@@ -135,6 +260,22 @@ fn criterion_benchmark(c: &mut Criterion) {
         indexlist_iter(black_box(count))));
     c.bench_function("linkedlist-iter", |b| b.iter(||
         linkedlist_iter(black_box(count))));
+    let keys = lru_keys(count * 4);
+    c.bench_function("indexlist-lru", |b| b.iter(||
+        indexlist_lru(black_box(&keys))));
+    c.bench_function("lru-lru", |b| b.iter(||
+        lru_lru(black_box(&keys))));
+    c.bench_function("linkedhashmap-lru", |b| b.iter(||
+        linkedhashmap_lru(black_box(&keys))));
+    c.bench_function("indexlist-churn", |b| b.iter(||
+        indexlist_churn(black_box(count))));
+    c.bench_function("linkedlist-churn", |b| b.iter(||
+        linkedlist_churn(black_box(count))));
+    let picks = handles(count);
+    c.bench_function("indexlist-handles", |b| b.iter(||
+        indexlist_handles(black_box(count), black_box(&picks))));
+    c.bench_function("slotmap-handles", |b| b.iter(||
+        slotmap_handles(black_box(count), black_box(&picks))));
     }
 
 criterion_group!(benches, criterion_benchmark);
@@ -13,20 +13,92 @@
 //! A new IndexList can be created empty with the `new` method, or created from
 //! an existing vector with `IndexList::from`.
 //!
+//! No index passed to an `IndexList` method can make it panic. An invalid
+//! index makes a lookup return `None` or `false` instead, and an insert fall
+//! back to an end of the list, unless the `IndexPolicy::Panic` policy is set,
+//! or `IndexPolicy::DebugPanic` in a debug build.
+//!
 #![forbid(unsafe_code)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
+pub mod listallocation;
+pub mod listbounded;
+pub mod listcells;
+#[cfg(feature = "fail-fast")]
+pub mod listchecked;
+pub mod listcursor;
+pub mod listcursormut;
+#[cfg(feature = "embedded")]
+pub mod listcritical;
+pub mod listdrainentries;
 pub mod listdrainiter;
+pub mod listdrainrange;
+pub mod listdrainwhile;
+pub mod listerror;
+pub mod listextractif;
+pub mod listforward;
+pub mod listguard;
 pub mod listindex;
 pub mod listiter;
+pub mod listpolicy;
+pub mod listpool;
+pub mod listraw;
+pub mod listremap;
+pub mod listresumable;
+#[cfg(feature = "std")]
+pub mod listshared;
+pub mod liststicky;
+pub mod listweighted;
+mod listbitmap;
+#[cfg(feature = "bytemuck")]
+mod listbytes;
+mod listfinger;
 mod listnode;
+mod listpinned;
+#[cfg(feature = "rand")]
+mod listrand;
+mod listtags;
+#[cfg(all(kani, feature = "verification"))]
+mod listverify;
 mod listends;
 
-use std::{cmp::Ordering, default::Default, fmt};
-use std::iter::{Extend, FromIterator};
-use crate::{listnode::ListNode, listends::ListEnds};
+use core::{cmp::Ordering, default::Default, fmt, mem};
+#[cfg(feature = "std")]
+use std::{collections::{HashMap, HashSet}, hash::Hash};
+use core::iter::{Extend, FromIterator};
+use alloc::{string::String, vec, vec::Vec};
+use crate::{listbitmap::ListBitmap, listfinger::ListFinger, listnode::ListNode, listends::ListEnds,
+    listtags::ListTags};
 pub use crate::listindex::ListIndex as ListIndex;
+pub use crate::listallocation::ListAllocation as ListAllocation;
+pub use crate::listbounded::{BoundedList, Evict};
+pub use crate::listcells::ListCells as ListCells;
+#[cfg(feature = "fail-fast")]
+pub use crate::listchecked::CheckedIter as CheckedIter;
+pub use crate::listcursor::Cursor as Cursor;
+pub use crate::listcursormut::CursorMut as CursorMut;
+#[cfg(feature = "embedded")]
+pub use crate::listcritical::CriticalList as CriticalList;
+pub use crate::listguard::StructureGuard as StructureGuard;
+pub use crate::listweighted::WeightedList as WeightedList;
+#[cfg(feature = "std")]
+pub use crate::listshared::SharedIndexList as SharedIndexList;
+pub use crate::liststicky::StickyCursor as StickyCursor;
 pub use crate::listiter::ListIter as ListIter;
+pub use crate::listresumable::ResumableIter as ResumableIter;
+pub use crate::listpolicy::IndexPolicy as IndexPolicy;
+pub use crate::listpool::Pool as Pool;
+pub use crate::listraw::{RawEnds, RawNode, RawParts};
+pub use crate::listerror::ListError as ListError;
+pub use crate::listforward::{ForwardDrain, ForwardIter, ForwardList};
+pub use crate::listremap::RemapTable as RemapTable;
 pub use crate::listdrainiter::ListDrainIter as ListDrainIter;
+pub use crate::listdrainrange::ListDrainRange as ListDrainRange;
+pub use crate::listdrainwhile::ListDrainWhile as ListDrainWhile;
+pub use crate::listextractif::ListExtractIf as ListExtractIf;
+pub use crate::listdrainentries::ListDrainEntries as ListDrainEntries;
 pub type Index = ListIndex; // for backwards compatibility with 0.2.7
 
 /// Doubly-linked list implemented in safe Rust.
@@ -37,6 +109,12 @@ pub struct IndexList<T> {
     used: ListEnds,
     free: ListEnds,
     size: usize,
+    bits: ListBitmap,
+    finger: ListFinger,
+    tags: ListTags,
+    policy: IndexPolicy,
+    #[cfg(feature = "fail-fast")]
+    stamp: u64,
 }
 
 impl<T> Default for IndexList<T> {
@@ -47,6 +125,33 @@ impl<T> Default for IndexList<T> {
             used: ListEnds::new(),
             free: ListEnds::new(),
             size: 0,
+            bits: ListBitmap::new(),
+            finger: ListFinger::new(),
+            tags: ListTags::new(),
+            policy: IndexPolicy::default(),
+            #[cfg(feature = "fail-fast")]
+            stamp: listchecked::new_stamp(),
+        }
+    }
+}
+
+impl<T> Clone for IndexList<T>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        IndexList::<T> {
+            elems: self.elems.clone(),
+            nodes: self.nodes.clone(),
+            used: self.used.clone(),
+            free: self.free.clone(),
+            size: self.size,
+            bits: self.bits.clone(),
+            finger: ListFinger::new(),
+            tags: self.tags.clone(),
+            policy: self.policy,
+            #[cfg(feature = "fail-fast")]
+            stamp: listchecked::new_stamp(),
         }
     }
 }
@@ -80,8 +185,41 @@ impl<T> IndexList<T> {
             used: ListEnds::new(),
             free: ListEnds::new(),
             size: 0,
+            bits: ListBitmap::with_capacity(capacity),
+            finger: ListFinger::new(),
+            tags: ListTags::new(),
+            policy: IndexPolicy::default(),
+            #[cfg(feature = "fail-fast")]
+            stamp: listchecked::new_stamp(),
         }
     }
+    /// Returns the policy for handling invalid indexes.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::{IndexList, IndexPolicy};
+    /// let list = IndexList::<u64>::new();
+    /// assert_eq!(list.index_policy(), IndexPolicy::Lenient);
+    /// ```
+    #[inline]
+    pub fn index_policy(&self) -> IndexPolicy {
+        self.policy
+    }
+    /// Select the policy for handling an invalid index given to a mutating
+    /// call that would otherwise fall back to a default behavior.
+    ///
+    /// Example:
+    /// ```rust,should_panic
+    /// # use index_list::{IndexList, IndexPolicy, ListIndex};
+    /// let mut list = IndexList::<u64>::new();
+    /// list.set_index_policy(IndexPolicy::Panic);
+    /// list.insert_before(ListIndex::new(), 42);
+    /// list.insert_before(ListIndex::from(7usize), 42); // panics
+    /// ```
+    #[inline]
+    pub fn set_index_policy(&mut self, policy: IndexPolicy) {
+        self.policy = policy;
+    }
     /// Returns the current capacity of the list.
     ///
     /// This value is always greater than or equal to the length.
@@ -115,12 +253,22 @@ impl<T> IndexList<T> {
     }
     /// Clears the list be removing all elements, making it empty.
     ///
+    /// The allocated memory is kept for reuse. Only elements that need to be
+    /// dropped are visited, so for plain data, such as integers, this takes
+    /// constant time no matter how long the list is.
+    ///
+    /// All indexes are invalid after the call, and are reported as not used
+    /// until they are handed out again by later inserts. This includes slots
+    /// taken with `acquire_free_slot`, which are taken back.
+    ///
     /// Example:
     /// ```rust
     /// # use index_list::IndexList;
     /// # let mut list = IndexList::<u64>::new();
+    /// # let index = list.insert_last(1);
     /// list.clear();
     /// assert!(list.is_empty());
+    /// assert!(!list.is_index_used(index));
     /// ```
     #[inline]
     pub fn clear(&mut self) {
@@ -129,6 +277,9 @@ impl<T> IndexList<T> {
         self.used.clear();
         self.free.clear();
         self.size = 0;
+        self.bits.clear();
+        self.tags.clear();
+        self.changed();
     }
     /// Returns `true` when the list is empty.
     ///
@@ -269,26 +420,174 @@ impl<T> IndexList<T> {
         }
         index
     }
+    /// Returns the index of the element at position `n` in the list, counting
+    /// from zero, or `None` if the list is shorter than that.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// # let list = IndexList::from(&mut vec!["A", "B", "C", "D", "E"]);
+    /// let index = list.get_nth(3);
+    /// assert_eq!(list.get(index), Some(&"D"));
+    /// assert_eq!(list.get(list.get_nth(2)), Some(&"C"));
+    /// assert!(list.get_nth(5).is_none());
+    /// ```
+    pub fn get_nth(&self, n: usize) -> ListIndex {
+        if n >= self.len() {
+            return ListIndex::new();
+        }
+        let mut start = (self.first_index(), 0);
+        if self.len() - 1 - n < n {
+            start = (self.last_index(), self.len() - 1);
+        }
+        if let Some((index, pos)) = self.finger.get() {
+            if pos.abs_diff(n) < start.1.abs_diff(n) {
+                start = (index, pos);
+            }
+        }
+        let (mut index, mut pos) = start;
+        while pos < n {
+            index = self.next_index(index);
+            pos += 1;
+        }
+        while pos > n {
+            index = self.prev_index(index);
+            pos -= 1;
+        }
+        self.finger.set(index, n);
+        index
+    }
+    /// Returns the position of the index in the list, counting from zero, or
+    /// `None` if the index is not valid.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// # let list = IndexList::from(&mut vec!["A", "B", "C", "D", "E"]);
+    /// let index = list.move_index(list.first_index(), 1);
+    /// assert_eq!(list.position_of(index), Some(1));
+    /// assert_eq!(list.position_of(list.next_index(index)), Some(2));
+    /// ```
+    pub fn position_of(&self, index: ListIndex) -> Option<usize> {
+        if !self.is_index_used(index) {
+            return None;
+        }
+        let finger = self.finger.get();
+        let mut back = index;
+        let mut fwd = index;
+        let mut steps = 0;
+        let pos = loop {
+            if back == self.first_index() {
+                break steps;
+            }
+            if fwd == self.last_index() {
+                break self.len() - 1 - steps;
+            }
+            if let Some((at, pos)) = finger {
+                if back == at {
+                    break pos + steps;
+                }
+                if fwd == at {
+                    break pos - steps;
+                }
+            }
+            back = self.prev_index(back);
+            fwd = self.next_index(fwd);
+            steps += 1;
+        };
+        self.finger.set(index, pos);
+        Some(pos)
+    }
+    /// Move to an index `steps` number of elements away, like `move_index`,
+    /// but stop at the last valid index when an end of the list is reached.
+    ///
+    /// Returns the index that was reached and the number of steps that could
+    /// not be taken, which is zero when the full distance was covered. The
+    /// remaining steps have the same sign as `steps`. If `index` is invalid
+    /// then it is returned together with all of the steps.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// # let list = IndexList::from(&mut vec!["A", "B", "C", "D", "E"]);
+    /// let first = list.first_index();
+    /// let (index, left) = list.move_index_checked(first, 3);
+    /// assert_eq!((list.get(index), left), (Some(&"D"), 0));
+    /// let (index, left) = list.move_index_checked(index, 5);
+    /// assert_eq!((list.get(index), left), (Some(&"E"), 4));
+    /// let (index, left) = list.move_index_checked(index, -7);
+    /// assert_eq!((index, left), (first, -3));
+    /// ```
+    pub fn move_index_checked(&self, index: ListIndex, steps: i32) -> (ListIndex, i32) {
+        if !self.is_index_used(index) {
+            return (index, steps);
+        }
+        let mut index = index;
+        let mut left = steps;
+        while left != 0 {
+            let next = if left > 0 {
+                self.next_index(index)
+            } else {
+                self.prev_index(index)
+            };
+            if next.is_none() {
+                break;
+            }
+            index = next;
+            left -= left.signum();
+        }
+        (index, left)
+    }
+    /// Move to an index `steps` number of elements away, like `move_index`,
+    /// but saturate at the first or last element instead of returning `None`
+    /// when walking past an end.
+    ///
+    /// An invalid index is returned unchanged.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// # let list = IndexList::from(&mut vec!["A", "B", "C", "D", "E"]);
+    /// let mut index = list.first_index();
+    /// index = list.move_index_clamped(index, 10);
+    /// assert_eq!(list.get(index), Some(&"E"));
+    /// index = list.move_index_clamped(index, -2);
+    /// assert_eq!(list.get(index), Some(&"C"));
+    /// index = list.move_index_clamped(index, -10);
+    /// assert_eq!(list.get(index), Some(&"A"));
+    /// ```
+    #[inline]
+    pub fn move_index_clamped(&self, index: ListIndex, steps: i32) -> ListIndex {
+        self.move_index_checked(index, steps).0
+    }
     /// Make the index `this` (and associated element) come before the index `that` (and associated element).
     ///
     /// Returns `true` if the operation was successful. This will fail if either index is invalid or if `this` and `that`
     /// are the same index.
     ///
+    /// If `that` is `None` then `this` is moved to the front of the list, just as `insert_before` inserts first
+    /// when given `None`.
+    ///
     /// This is similar to calling `let elem = self.remove(this);` followed by `self.insert_before(that, elem)`
     /// except that it doesn't invalildate or change the index `this`. That is, the index `this` is guaranteed
     /// to still point to the same element `elem` after this operation completes.
     ///
     /// Example:
     /// ```rust
-    /// # use index_list::IndexList;
+    /// # use index_list::{IndexList, ListIndex};
     /// let mut list = IndexList::from(&mut vec![1, 2, 3]);
     /// let index = list.first_index();
     /// let moved = list.shift_index_before(index, list.last_index());
     /// assert!(moved);
     /// assert_eq!(list.get(index), Some(&1));
     /// assert_eq!(list.to_string(), "[2 >< 1 >< 3]");
+    /// list.shift_index_before(list.last_index(), ListIndex::new());
+    /// assert_eq!(list.to_string(), "[3 >< 2 >< 1]");
     /// ```
     pub fn shift_index_before(&mut self, this: ListIndex, that: ListIndex) -> bool {
+        if that.is_none() {
+            return self.shift_index_to_front(this);
+        }
         let valid = self.is_index_used(this) && self.is_index_used(that) && this != that;
         if valid {
             self.linkout_used(this);
@@ -301,13 +600,16 @@ impl<T> IndexList<T> {
     /// Returns `true` if the operation was successful. This will fail if either index is invalid or if `this` and `that`
     /// are the same index.
     ///
+    /// If `that` is `None` then `this` is moved to the back of the list, just as `insert_after` inserts last when
+    /// given `None`.
+    ///
     /// This is similar to calling `let elem = self.remove(this);` followed by `self.insert_after(that, elem)`
     /// except that it doesn't invalildate or change the index `this`. That is, the index `this` is guaranteed
     /// to still point to the same element `elem` after this operation completes.
     ///
     /// Example:
     /// ```rust
-    /// # use index_list::IndexList;
+    /// # use index_list::{IndexList, ListIndex};
     /// let mut list = IndexList::from(&mut vec![1, 2, 3]);
     /// let index = list.first_index();
     /// let next_index = list.next_index(index);
@@ -315,8 +617,13 @@ impl<T> IndexList<T> {
     /// assert!(moved);
     /// assert_eq!(list.get(index), Some(&1));
     /// assert_eq!(list.to_string(), "[2 >< 1 >< 3]");
+    /// list.shift_index_after(list.first_index(), ListIndex::new());
+    /// assert_eq!(list.to_string(), "[1 >< 3 >< 2]");
     /// ```
     pub fn shift_index_after(&mut self, this: ListIndex, that: ListIndex) -> bool {
+        if that.is_none() {
+            return self.shift_index_to_back(this);
+        }
         let valid = self.is_index_used(this) && self.is_index_used(that) && this != that;
         if valid {
             self.linkout_used(this);
@@ -376,6 +683,465 @@ impl<T> IndexList<T> {
         }
         valid
     }
+    /// Mark the index `this` as the most recently used, by making it come
+    /// last in the list.
+    ///
+    /// Returns `true` if the operation was successful. This will fail if
+    /// `this` is an invalid index. An element that is already last is left
+    /// where it is.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 2, 3]);
+    /// let index = list.first_index();
+    /// assert!(list.touch(index));
+    /// assert_eq!(list.to_string(), "[2 >< 3 >< 1]");
+    /// ```
+    pub fn touch(&mut self, this: ListIndex) -> bool {
+        let valid = self.is_index_used(this);
+        if valid {
+            self.relink_last(this);
+        }
+        valid
+    }
+    /// Rotate the list `n` steps to the left, so that the element in
+    /// position `n` comes first and the elements before it come last.
+    ///
+    /// Only the ends of the list are relinked, after a walk of at most half
+    /// the list to find the new first element, and every index stays valid.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 2, 3, 4, 5]);
+    /// let index = list.first_index();
+    /// list.rotate_left(2);
+    /// assert_eq!(list.to_string(), "[3 >< 4 >< 5 >< 1 >< 2]");
+    /// assert_eq!(list.get(index), Some(&1));
+    /// list.rotate_left(7);
+    /// assert_eq!(list.to_string(), "[5 >< 1 >< 2 >< 3 >< 4]");
+    /// ```
+    pub fn rotate_left(&mut self, n: usize) {
+        let len = self.size;
+        let n = n.checked_rem(len).unwrap_or(0);
+        if n == 0 {
+            return;
+        }
+        let mut head = self.used.head;
+        if n <= len / 2 {
+            (0..n).for_each(|_| head = self.next_index(head));
+        } else {
+            head = self.used.tail;
+            (n..len - 1).for_each(|_| head = self.prev_index(head));
+        }
+        let (first, last) = (self.used.head, self.used.tail);
+        let tail = self.set_prev(head, ListIndex::new());
+        self.set_next(tail, ListIndex::new());
+        self.set_next(last, first);
+        self.set_prev(first, last);
+        self.used.new_head(head);
+        self.used.new_tail(tail);
+    }
+    /// Rotate the list `n` steps to the right, so that the last `n` elements
+    /// come first, see `rotate_left`.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 2, 3, 4, 5]);
+    /// list.rotate_right(2);
+    /// assert_eq!(list.to_string(), "[4 >< 5 >< 1 >< 2 >< 3]");
+    /// ```
+    pub fn rotate_right(&mut self, n: usize) {
+        let len = self.size;
+        if len > 0 {
+            self.rotate_left(len - n % len);
+        }
+    }
+    /// Swap the places of two elements in the list by relinking them, so that
+    /// each element keeps its index and no element data is moved.
+    ///
+    /// Returns `true` if the places were swapped, or if both indexes are the
+    /// same. This will fail, and leave the list unchanged, if either index
+    /// is invalid.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 2, 3, 4]);
+    /// let (first, last) = (list.first_index(), list.last_index());
+    /// assert!(list.swap_positions(first, last));
+    /// assert_eq!(list.to_string(), "[4 >< 2 >< 3 >< 1]");
+    /// assert_eq!(list.get(first), Some(&1));
+    /// ```
+    pub fn swap_positions(&mut self, this: ListIndex, that: ListIndex) -> bool {
+        if !self.is_index_used(this) || !self.is_index_used(that) {
+            return false;
+        }
+        if this == that {
+            return true;
+        }
+        if self.next_index(this) == that {
+            return self.shift_index_after(this, that);
+        }
+        if self.next_index(that) == this {
+            return self.shift_index_after(that, this);
+        }
+        let prev = self.prev_index(this);
+        self.shift_index_after(this, that);
+        if prev.is_none() {
+            self.shift_index_to_front(that)
+        } else {
+            self.shift_index_after(that, prev)
+        }
+    }
+    /// Move the element at the index one step towards the end, by swapping
+    /// places with the next element.
+    ///
+    /// Returns `false` if the index is invalid or already last.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec!["a", "b", "c"]);
+    /// let index = list.first_index();
+    /// assert!(list.swap_with_next(index));
+    /// assert_eq!(list.to_string(), "[b >< a >< c]");
+    /// assert!(!list.swap_with_next(list.last_index()));
+    /// ```
+    pub fn swap_with_next(&mut self, index: ListIndex) -> bool {
+        if !self.is_index_used(index) {
+            return false;
+        }
+        let next = self.next_index(index);
+        next.is_some() && self.shift_index_after(index, next)
+    }
+    /// Move the element at the index one step towards the front, by swapping
+    /// places with the previous element.
+    ///
+    /// Returns `false` if the index is invalid or already first.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec!["a", "b", "c"]);
+    /// let index = list.last_index();
+    /// assert!(list.swap_with_prev(index));
+    /// assert_eq!(list.to_string(), "[a >< c >< b]");
+    /// assert!(!list.swap_with_prev(list.first_index()));
+    /// ```
+    pub fn swap_with_prev(&mut self, index: ListIndex) -> bool {
+        if !self.is_index_used(index) {
+            return false;
+        }
+        let prev = self.prev_index(index);
+        prev.is_some() && self.shift_index_before(index, prev)
+    }
+    /// Update the element data at the index with a closure and then make it
+    /// come last in the list, as a single operation.
+    ///
+    /// Returns the result of the closure, or `None` if the index is invalid,
+    /// in which case the list is not changed.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 2, 3]);
+    /// let index = list.first_index();
+    /// let hits = list.update_and_touch(index, |data| {
+    ///     *data += 10;
+    ///     *data
+    /// });
+    /// assert_eq!(hits, Some(11));
+    /// assert_eq!(list.to_string(), "[2 >< 3 >< 11]");
+    /// ```
+    pub fn update_and_touch<R, F>(&mut self, this: ListIndex, f: F) -> Option<R>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        let result = self.get_mut(this).map(f)?;
+        self.relink_last(this);
+        Some(result)
+    }
+    /// Move the index `this` (and associated element) to its place in an
+    /// otherwise sorted list, after its sort key has changed.
+    ///
+    /// Returns `true` if the operation was successful. This will fail if
+    /// `this` is an invalid index.
+    ///
+    /// The list is walked from the current position of the element, in the
+    /// direction it needs to go, so a small change of key makes a short walk.
+    /// The element is placed after any equal elements when it moves towards
+    /// the front, and before them when it moves towards the back. Unlike
+    /// removing and inserting it again, the index `this` is not changed.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 3, 5, 7, 9]);
+    /// let index = list.get_nth(3);
+    /// list.update(index, |data| *data = 2);
+    /// assert!(list.resort_index(index, |a, b| a.cmp(b)));
+    /// assert_eq!(list.to_string(), "[1 >< 2 >< 3 >< 5 >< 9]");
+    /// list.update(index, |data| *data = 10);
+    /// assert!(list.resort_index(index, |a, b| a.cmp(b)));
+    /// assert_eq!(list.to_string(), "[1 >< 3 >< 5 >< 9 >< 10]");
+    /// ```
+    pub fn resort_index<F>(&mut self, this: ListIndex, mut cmp: F) -> bool
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let elem = match self.get(this) {
+            Some(elem) => elem,
+            None => return false,
+        };
+        let mut prev = self.prev_index(this);
+        if self.get(prev).is_some_and(|p| cmp(p, elem) == Ordering::Greater) {
+            while self.get(prev).is_some_and(|p| cmp(p, elem) == Ordering::Greater) {
+                prev = self.prev_index(prev);
+            }
+            return if prev.is_none() {
+                self.shift_index_to_front(this)
+            } else {
+                self.shift_index_after(this, prev)
+            };
+        }
+        let mut next = self.next_index(this);
+        if self.get(next).is_some_and(|n| cmp(n, elem) == Ordering::Less) {
+            while self.get(next).is_some_and(|n| cmp(n, elem) == Ordering::Less) {
+                next = self.next_index(next);
+            }
+            return if next.is_none() {
+                self.shift_index_to_back(this)
+            } else {
+                self.shift_index_before(this, next)
+            };
+        }
+        true
+    }
+    /// Relink the list so that all elements where the predicate matches come
+    /// before all the elements where it does not, keeping the order within
+    /// each group.
+    ///
+    /// Returns the first index of the elements that did not match, which is
+    /// `None` if all of them matched. No element data is moved, so all the
+    /// indexes remain valid.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 2, 3, 4, 5, 6]);
+    /// let split = list.partition_in_place(|n| n % 2 == 0);
+    /// assert_eq!(list.to_string(), "[2 >< 4 >< 6 >< 1 >< 3 >< 5]");
+    /// assert_eq!(list.get(split), Some(&1));
+    /// ```
+    pub fn partition_in_place<F>(&mut self, mut pred: F) -> ListIndex
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut tail = ListIndex::new();
+        let mut index = self.first_index();
+        while let Some(elem) = self.get(index) {
+            let next = self.next_index(index);
+            if pred(elem) {
+                if tail.is_none() {
+                    if index != self.first_index() {
+                        self.shift_index_to_front(index);
+                    }
+                } else if self.next_index(tail) != index {
+                    self.shift_index_after(index, tail);
+                }
+                tail = index;
+            }
+            index = next;
+        }
+        self.next_index(tail)
+    }
+    /// Sort the list in ascending order.
+    ///
+    /// The sort is a stable merge sort that only relinks the elements, so no
+    /// element data is moved and every index still refers to the same
+    /// element afterwards.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![3, 1, 2]);
+    /// let first = list.first_index();
+    /// list.sort();
+    /// assert_eq!(list.to_string(), "[1 >< 2 >< 3]");
+    /// assert_eq!(list.get(first), Some(&3));
+    /// assert_eq!(list.last_index(), first);
+    /// ```
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.merge_sort(T::cmp, false);
+    }
+    /// Sort the list with the comparison function, see `sort`. Elements that
+    /// compare equal keep their order.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![(2, 'a'), (1, 'b'), (2, 'c')]);
+    /// list.sort_by(|a, b| a.0.cmp(&b.0));
+    /// let order: String = list.iter().map(|e| e.1).collect();
+    /// assert_eq!(order, "bac");
+    /// ```
+    pub fn sort_by<F>(&mut self, cmp: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.merge_sort(cmp, false);
+    }
+    /// Sort the list with the comparison function and remove the duplicates,
+    /// keeping the first of each run of elements that compare equal.
+    ///
+    /// Returns the number of elements that were removed.
+    ///
+    /// The duplicates are dropped while the sorted runs are merged, so the
+    /// list is only walked by the merge sort itself. The sort relinks the
+    /// elements without moving any element data, so the indexes of the kept
+    /// elements remain valid.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![3, 1, 3, 2, 1]);
+    /// let first = list.first_index();
+    /// assert_eq!(list.sort_dedup_by(|a, b| a.cmp(b)), 2);
+    /// assert_eq!(list.to_string(), "[1 >< 2 >< 3]");
+    /// assert_eq!(list.get(first), Some(&3));
+    /// ```
+    pub fn sort_dedup_by<F>(&mut self, cmp: F) -> usize
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.merge_sort(cmp, true)
+    }
+    /// Returns the indexes of all the elements, in the order given by the
+    /// comparison function, without changing the list. Equal elements keep
+    /// their order in the list.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let list = IndexList::from(&mut vec!["pear", "fig", "apple"]);
+    /// let sorted = list.sorted_indexes_by(|a, b| a.len().cmp(&b.len()));
+    /// let view: Vec<_> = sorted.iter().filter_map(|&index| list.get(index)).collect();
+    /// assert_eq!(view, vec![&"fig", &"pear", &"apple"]);
+    /// assert_eq!(list.to_string(), "[pear >< fig >< apple]");
+    /// ```
+    pub fn sorted_indexes_by<F>(&self, mut cmp: F) -> Vec<ListIndex>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut indexes = self.indexes();
+        indexes.sort_by(|&a, &b| self.ordering(a, b, &mut cmp));
+        indexes
+    }
+    /// Returns the element that would be at position `k` if the list was
+    /// sorted with the comparison function, together with its index, or
+    /// `None` if `k` is not less than the length.
+    ///
+    /// This uses a selection over a temporary buffer of indexes, which takes
+    /// linear time on average instead of sorting, and the list is not changed.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let list = IndexList::from(&mut vec![7, 1, 5, 3, 9]);
+    /// let (index, median) = list.select_nth_by(2, |a, b| a.cmp(b)).unwrap();
+    /// assert_eq!(median, &5);
+    /// assert_eq!(list.get(index), Some(&5));
+    /// assert!(list.select_nth_by(5, |a, b| a.cmp(b)).is_none());
+    /// ```
+    pub fn select_nth_by<F>(&self, k: usize, mut cmp: F) -> Option<(ListIndex, &T)>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        if k >= self.len() {
+            return None;
+        }
+        let mut indexes = self.indexes();
+        let (_, &mut index, _) =
+            indexes.select_nth_unstable_by(k, |&a, &b| self.ordering(a, b, &mut cmp));
+        self.get(index).map(|elem| (index, elem))
+    }
+    /// Freeze the structure of the list for as long as the returned guard is
+    /// held.
+    ///
+    /// No elements can be inserted, removed or moved through the guard, so
+    /// indexes can be cached with confidence that they remain valid, while
+    /// the element data can still be changed.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 2, 3]);
+    /// let mut frozen = list.freeze_structure();
+    /// let cached: Vec<_> = (0..frozen.len()).map(|n| frozen.get_nth(n)).collect();
+    /// for &index in &cached {
+    ///     frozen.update(index, |data| *data *= 2);
+    /// }
+    /// assert_eq!(frozen.to_string(), "[2 >< 4 >< 6]");
+    /// ```
+    #[inline]
+    pub fn freeze_structure(&mut self) -> StructureGuard<'_, T> {
+        StructureGuard::new(self)
+    }
+    /// Get a view of the list where the elements are cells, so that several
+    /// of them can be read and changed at the same time, see `ListCells`.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 2]);
+    /// let cells = list.as_cells();
+    /// let (first, last) = (cells.first_index(), cells.last_index());
+    /// cells.set(first, cells.get(last).unwrap());
+    /// cells.set(last, 0);
+    /// assert_eq!(list.to_string(), "[2 >< 0]");
+    /// ```
+    #[inline]
+    pub fn as_cells(&mut self) -> ListCells<'_, T> {
+        ListCells::new(&mut self.elems, &self.nodes, &self.used)
+    }
+    /// Returns a stamp that changes whenever the structure of the list
+    /// changes, that is when elements are inserted, removed or moved, or
+    /// the list is cleared. Changing element data does not change it.
+    ///
+    /// An iterator borrows the list, so the compiler already rules out
+    /// changes while it is alive. A walk using `next_index` and `prev_index`
+    /// does not, and can save the stamp when it starts and compare it at each
+    /// step to fail fast instead of following links that have changed, which
+    /// is what `iter_checked` does.
+    ///
+    /// Each new list, including a clone, starts with a stamp that no earlier
+    /// list had, so replacing the list in place changes the stamp as well,
+    /// except on targets without 32-bit atomics.
+    ///
+    /// Only available with the `fail-fast` feature.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 2, 3]);
+    /// let stamp = list.structure_stamp();
+    /// let index = list.first_index();
+    /// list.update(index, |data| *data = 10);
+    /// assert_eq!(list.structure_stamp(), stamp);
+    /// list.remove_last();
+    /// assert_ne!(list.structure_stamp(), stamp);
+    /// ```
+    #[cfg(feature = "fail-fast")]
+    #[inline]
+    pub fn structure_stamp(&self) -> u64 {
+        self.stamp
+    }
     /// Get a reference to the first element data, or `None`.
     ///
     /// Example:
@@ -464,34 +1230,145 @@ impl<T> IndexList<T> {
     /// ```
     #[inline]
     pub fn get_mut(&mut self, index: ListIndex) -> Option<&mut T> {
-        if let Some(ndx) = index.get() {
-            if ndx < self.capacity() {
-                return self.elems[ndx].as_mut();
-            }
-        }
-        None
+        self.elems.get_mut(index.get()?)?.as_mut()
     }
-    /// Swap the element data between two indexes.
+    /// Get the tag of the element at the index, or `None` if the index is not
+    /// valid.
+    ///
+    /// With the `tags` feature every element has a small tag next to its
+    /// links, for flags and other metadata that should not be part of the
+    /// element data. The tag is `0` for a newly inserted element, and it
+    /// follows the element when moved.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec!["job1", "job2"]);
+    /// let index = list.first_index();
+    /// assert_eq!(list.get_tag(index), Some(0));
+    /// ```
+    #[cfg(feature = "tags")]
+    #[inline]
+    pub fn get_tag(&self, index: ListIndex) -> Option<u32> {
+        let at = index.get().filter(|_| self.is_index_used(index))?;
+        Some(self.tags.get(at))
+    }
+    /// Set the tag of the element at the index and return the old tag, or
+    /// `None` if the index is not valid.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// const PINNED: u32 = 1;
+    /// let mut list = IndexList::from(&mut vec!["job1", "job2"]);
+    /// let index = list.last_index();
+    /// assert_eq!(list.set_tag(index, PINNED), Some(0));
+    /// assert_eq!(list.get_tag(index), Some(PINNED));
+    /// list.remove(index);
+    /// assert_eq!(list.set_tag(index, PINNED), None);
+    /// ```
+    #[cfg(feature = "tags")]
+    pub fn set_tag(&mut self, index: ListIndex, tag: u32) -> Option<u32> {
+        let at = index.get().filter(|_| self.is_index_used(index))?;
+        Some(self.tags.set(at, tag, self.capacity()))
+    }
+    /// Update the element data at the index with a closure and return its
+    /// result, or `None` if the index is not valid.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// # let mut list = IndexList::from(&mut vec![1, 2, 3]);
+    /// # let index = list.last_index();
+    /// let old = list.update(index, |data| {
+    ///     *data *= 10;
+    ///     *data / 10
+    /// });
+    /// assert_eq!(old, Some(3));
+    /// assert_eq!(list.get(index), Some(&30));
+    /// ```
+    #[inline]
+    pub fn update<R, F>(&mut self, index: ListIndex, f: F) -> Option<R>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        self.get_mut(index).map(f)
+    }
+    /// Replace the data of the element at the index and return the old data.
+    ///
+    /// The element keeps its index and position. Returns `None` if the index
+    /// is not valid, in which case `elem` is dropped and the list is
+    /// unchanged.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec!["a", "b"]);
+    /// let index = list.last_index();
+    /// assert_eq!(list.replace(index, "c"), Some("b"));
+    /// assert_eq!(list.to_string(), "[a >< c]");
+    /// ```
+    #[inline]
+    pub fn replace(&mut self, index: ListIndex, elem: T) -> Option<T> {
+        self.get_mut(index).map(|data| mem::replace(data, elem))
+    }
+    /// Replace the data of the first element and return the old data.
+    ///
+    /// The element keeps its index and position. Returns `None` if the list
+    /// is empty, in which case `elem` is dropped and the list is unchanged.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 2, 3]);
+    /// let index = list.first_index();
+    /// assert_eq!(list.replace_first(4), Some(1));
+    /// assert_eq!(list.get(index), Some(&4));
+    /// ```
+    #[inline]
+    pub fn replace_first(&mut self, elem: T) -> Option<T> {
+        self.get_mut_first().map(|data| mem::replace(data, elem))
+    }
+    /// Replace the data of the last element and return the old data.
     ///
-    /// Both indexes must be valid.
+    /// The element keeps its index and position. Returns `None` if the list
+    /// is empty, in which case `elem` is dropped and the list is unchanged.
     ///
     /// Example:
     /// ```rust
     /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 2, 3]);
+    /// let index = list.last_index();
+    /// assert_eq!(list.replace_last(4), Some(3));
+    /// assert_eq!(list.get(index), Some(&4));
+    /// ```
+    #[inline]
+    pub fn replace_last(&mut self, elem: T) -> Option<T> {
+        self.get_mut_last().map(|data| mem::replace(data, elem))
+    }
+    /// Swap the element data between two indexes.
+    ///
+    /// Returns `true` if the data was swapped. This will fail, and leave the
+    /// list unchanged, if either index is invalid.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::{IndexList, ListIndex};
     /// # let mut list = IndexList::<u64>::new();
     /// # list.insert_first(1);
     /// # list.insert_last(2);
-    /// list.swap_index(list.first_index(), list.last_index());
+    /// assert!(list.swap_index(list.first_index(), list.last_index()));
     /// # assert_eq!(list.get_first(), Some(&2u64));
     /// # assert_eq!(list.get_last(), Some(&1u64));
+    /// assert!(!list.swap_index(list.first_index(), ListIndex::new()));
     /// ```
     #[inline]
-    pub fn swap_index(&mut self, this: ListIndex, that: ListIndex) {
-        if let Some(here) = this.get() {
-            if let Some(there) = that.get() {
-                self.swap_data(here, there);
-            }
+    pub fn swap_index(&mut self, this: ListIndex, that: ListIndex) -> bool {
+        let valid = self.is_index_used(this) && self.is_index_used(that);
+        if let (true, Some(here), Some(there)) = (valid, this.get(), that.get()) {
+            self.swap_data(here, there);
         }
+        valid
     }
     /// Peek at next element data, after the index, if any.
     ///
@@ -547,7 +1424,7 @@ impl<T> IndexList<T> {
     where
         T: PartialEq,
     {
-        self.elems.contains(&Some(elem))
+        self.index_of(elem).is_some()
     }
     /// Returns the index of the element containg the data.
     ///
@@ -566,14 +1443,42 @@ impl<T> IndexList<T> {
     where
         T: PartialEq,
     {
-        ListIndex::from(self.elems.iter().position(|e| {
-            if let Some(data) = e {
+        ListIndex::from(self.bits.iter_used().find(|&at| {
+            if let Some(Some(data)) = self.elems.get(at) {
                 data == &elem
             } else {
                 false
             }
         }))
     }
+    /// Returns `true` when both lists hold the same elements, the same number
+    /// of times, regardless of their order.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let list = IndexList::from(&mut vec![1, 2, 2, 3]);
+    /// assert!(list.eq_ignore_order(&IndexList::from(&mut vec![2, 3, 2, 1])));
+    /// assert!(!list.eq_ignore_order(&IndexList::from(&mut vec![1, 2, 3, 3])));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn eq_ignore_order(&self, other: &IndexList<T>) -> bool
+    where
+        T: Eq + Hash,
+    {
+        if self.len() != other.len() {
+            return false;
+        }
+        let mut counts = HashMap::<&T, usize>::with_capacity(self.len());
+        self.iter().for_each(|elem| *counts.entry(elem).or_default() += 1);
+        other.iter().all(|elem| match counts.get_mut(elem) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                true
+            }
+            _ => false,
+        })
+    }
     /// Insert a new element at the beginning.
     ///
     /// It is usually not necessary to keep the index, as the element data
@@ -609,6 +1514,8 @@ impl<T> IndexList<T> {
     /// Insert a new element before the index.
     ///
     /// If the index is `None` then the new element will be inserted first.
+    /// An invalid index is handled by the index policy, which by default
+    /// also inserts first, or use `try_insert_before` to get an error instead.
     ///
     /// Example:
     /// ```rust
@@ -621,6 +1528,10 @@ impl<T> IndexList<T> {
         if index.is_none() {
             return self.insert_first(elem);
         }
+        if !self.is_index_used(index) {
+            self.policy.fallback("insert_before", index);
+            return self.insert_first(elem);
+        }
         let this = self.new_node(Some(elem));
         self.linkin_this_before_that(this, index);
         this
@@ -628,6 +1539,8 @@ impl<T> IndexList<T> {
     /// Insert a new element after the index.
     ///
     /// If the index is `None` then the new element will be inserted last.
+    /// An invalid index is handled by the index policy, which by default
+    /// also inserts last, or use `try_insert_after` to get an error instead.
     ///
     /// Example:
     /// ```rust
@@ -640,87 +1553,1311 @@ impl<T> IndexList<T> {
         if index.is_none() {
             return self.insert_last(elem);
         }
+        if !self.is_index_used(index) {
+            self.policy.fallback("insert_after", index);
+            return self.insert_last(elem);
+        }
         let this = self.new_node(Some(elem));
         self.linkin_this_after_that(this, index);
         this
     }
-    /// Remove the first element and return its data.
+    /// Insert a new element before the index, like `insert_before`, but
+    /// return an error if the index is invalid instead of falling back.
+    ///
+    /// If the index is `None` then the new element is inserted first. The
+    /// element is dropped when the index is invalid.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::{IndexList, ListError, ListIndex};
+    /// let mut list = IndexList::from(&mut vec![2]);
+    /// let stale = list.insert_last(3);
+    /// list.remove(stale);
+    /// assert_eq!(list.try_insert_before(stale, 0), Err(ListError::InvalidIndex(stale)));
+    /// assert!(list.try_insert_before(ListIndex::new(), 1).is_ok());
+    /// assert_eq!(list.to_string(), "[1 >< 2]");
+    /// ```
+    pub fn try_insert_before(&mut self, index: ListIndex, elem: T) -> Result<ListIndex, ListError> {
+        if index.is_some() && !self.is_index_used(index) {
+            return Err(ListError::InvalidIndex(index));
+        }
+        Ok(self.insert_before(index, elem))
+    }
+    /// Insert a new element after the index, like `insert_after`, but return
+    /// an error if the index is invalid instead of falling back.
+    ///
+    /// If the index is `None` then the new element is inserted last. The
+    /// element is dropped when the index is invalid.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::{IndexList, ListError, ListIndex};
+    /// let mut list = IndexList::from(&mut vec![1]);
+    /// let bogus = ListIndex::from(7usize);
+    /// assert!(list.try_insert_after(ListIndex::new(), 2).is_ok());
+    /// assert_eq!(list.try_insert_after(bogus, 3), Err(ListError::InvalidIndex(bogus)));
+    /// assert_eq!(list.to_string(), "[1 >< 2]");
+    /// ```
+    pub fn try_insert_after(&mut self, index: ListIndex, elem: T) -> Result<ListIndex, ListError> {
+        if index.is_some() && !self.is_index_used(index) {
+            return Err(ListError::InvalidIndex(index));
+        }
+        Ok(self.insert_after(index, elem))
+    }
+    /// Insert a new element at the beginning, made by a closure that is
+    /// given the index the element will have.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::{IndexList, ListIndex};
+    /// let mut list = IndexList::<(ListIndex, &str)>::new();
+    /// let index = list.insert_first_with(|me| (me, "first"));
+    /// assert_eq!(list.get(index), Some(&(index, "first")));
+    /// ```
+    pub fn insert_first_with<F>(&mut self, f: F) -> ListIndex
+    where
+        F: FnOnce(ListIndex) -> T,
+    {
+        let elem = f(self.next_free_index());
+        self.insert_first(elem)
+    }
+    /// Insert a new element at the end, made by a closure that is given the
+    /// index the element will have.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::{IndexList, ListIndex};
+    /// let mut list = IndexList::<ListIndex>::new();
+    /// let index = list.insert_last_with(|me| me);
+    /// assert_eq!(list.get(index), Some(&index));
+    /// ```
+    pub fn insert_last_with<F>(&mut self, f: F) -> ListIndex
+    where
+        F: FnOnce(ListIndex) -> T,
+    {
+        let elem = f(self.next_free_index());
+        self.insert_last(elem)
+    }
+    /// Insert a new element before the index, like `insert_before`, made by
+    /// a closure that is given the index the element will have.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::{IndexList, ListIndex};
+    /// let mut list = IndexList::<ListIndex>::new();
+    /// let last = list.insert_last_with(|me| me);
+    /// let index = list.insert_before_with(last, |me| me);
+    /// assert_eq!(list.get_first(), Some(&index));
+    /// ```
+    pub fn insert_before_with<F>(&mut self, index: ListIndex, f: F) -> ListIndex
+    where
+        F: FnOnce(ListIndex) -> T,
+    {
+        let elem = f(self.next_free_index());
+        self.insert_before(index, elem)
+    }
+    /// Insert a new element after the index, like `insert_after`, made by a
+    /// closure that is given the index the element will have.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::{IndexList, ListIndex};
+    /// let mut list = IndexList::<ListIndex>::new();
+    /// let first = list.insert_first_with(|me| me);
+    /// list.remove(first);
+    /// let index = list.insert_after_with(list.first_index(), |me| me);
+    /// assert_eq!(index, first);
+    /// assert_eq!(list.get_last(), Some(&index));
+    /// ```
+    pub fn insert_after_with<F>(&mut self, index: ListIndex, f: F) -> ListIndex
+    where
+        F: FnOnce(ListIndex) -> T,
+    {
+        let elem = f(self.next_free_index());
+        self.insert_after(index, elem)
+    }
+    /// Insert all the elements of the iterator, in order, after the index,
+    /// and return the index of the last one inserted, or `None` if there
+    /// were none.
+    ///
+    /// If the index is `None`, or invalid, then the elements are inserted
+    /// last, as with `insert_after`.
     ///
     /// Example:
     /// ```rust
     /// # use index_list::IndexList;
-    /// # let mut list = IndexList::<u64>::new();
-    /// # list.insert_first(42);
-    /// let data = list.remove_first();
-    /// # assert_eq!(data, Some(42));
+    /// let mut list = IndexList::from(&mut vec![1, 5]);
+    /// let last = list.splice_after(list.first_index(), 2..5);
+    /// assert_eq!(list.get(last), Some(&4));
+    /// assert_eq!(list.to_string(), "[1 >< 2 >< 3 >< 4 >< 5]");
     /// ```
-    pub fn remove_first(&mut self) -> Option<T> {
-        self.remove(self.first_index())
+    pub fn splice_after<I>(&mut self, index: ListIndex, iter: I) -> ListIndex
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut last = ListIndex::new();
+        let mut after = index;
+        for elem in iter {
+            after = self.insert_after(after, elem);
+            last = after;
+        }
+        last
     }
-    /// Remove the last element and return its data.
+    /// Insert all the elements of the iterator, in order, before the index,
+    /// and return the index of the last one inserted, or `None` if there
+    /// were none.
+    ///
+    /// If the index is `None`, or invalid, then the elements are inserted
+    /// first, as with `insert_before`.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::{IndexList, ListIndex};
+    /// let mut list = IndexList::from(&mut vec![1, 5]);
+    /// list.splice_before(list.last_index(), 2..5);
+    /// list.splice_before(ListIndex::new(), [-1, 0]);
+    /// assert_eq!(list.to_string(), "[-1 >< 0 >< 1 >< 2 >< 3 >< 4 >< 5]");
+    /// ```
+    pub fn splice_before<I>(&mut self, index: ListIndex, iter: I) -> ListIndex
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut last = ListIndex::new();
+        for elem in iter {
+            last = if last.is_none() {
+                self.insert_before(index, elem)
+            } else {
+                self.insert_after(last, elem)
+            };
+        }
+        last
+    }
+    /// Insert all the elements, in order, before the index, like
+    /// `splice_before`, and return the new indexes in the same order.
     ///
     /// Example:
     /// ```rust
     /// # use index_list::IndexList;
-    /// # let mut list = IndexList::<u64>::new();
-    /// # list.insert_last(42);
-    /// let data = list.remove_last();
-    /// # assert_eq!(data, Some(42));
+    /// let mut list = IndexList::from(&mut vec!["c"]);
+    /// let indexes = list.insert_many_before(list.first_index(), ["a", "b"]);
+    /// assert_eq!(list.get(indexes[1]), Some(&"b"));
+    /// assert_eq!(list.to_string(), "[a >< b >< c]");
     /// ```
-    pub fn remove_last(&mut self) -> Option<T> {
-        self.remove(self.last_index())
+    pub fn insert_many_before<I>(&mut self, index: ListIndex, items: I) -> Vec<ListIndex>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let items = items.into_iter();
+        let mut indexes: Vec<ListIndex> = Vec::with_capacity(items.size_hint().0);
+        for elem in items {
+            let this = match indexes.last() {
+                Some(&last) => self.insert_after(last, elem),
+                None => self.insert_before(index, elem),
+            };
+            indexes.push(this);
+        }
+        indexes
+    }
+    /// Insert all the elements, in order, after the index, like
+    /// `splice_after`, and return the new indexes in the same order.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec!["a"]);
+    /// let indexes = list.insert_many_after(list.first_index(), ["b", "c"]);
+    /// assert_eq!(indexes.len(), 2);
+    /// assert_eq!(list.get(indexes[1]), Some(&"c"));
+    /// assert_eq!(list.to_string(), "[a >< b >< c]");
+    /// ```
+    pub fn insert_many_after<I>(&mut self, index: ListIndex, items: I) -> Vec<ListIndex>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let items = items.into_iter();
+        let mut indexes = Vec::with_capacity(items.size_hint().0);
+        let mut after = index;
+        for elem in items {
+            after = self.insert_after(after, elem);
+            indexes.push(after);
+        }
+        indexes
+    }
+    /// Insert a new element at the beginning, but only if that can be done
+    /// without allocating memory.
+    ///
+    /// Returns the new index, or the element back as an error when there is
+    /// neither a free index to reuse nor any spare capacity.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::<u64>::with_capacity(1);
+    /// assert!(list.try_insert_first_within_capacity(1).is_ok());
+    /// assert_eq!(list.try_insert_first_within_capacity(2), Err(2));
+    /// ```
+    pub fn try_insert_first_within_capacity(&mut self, elem: T) -> Result<ListIndex, T> {
+        if !self.has_spare_capacity() {
+            return Err(elem);
+        }
+        Ok(self.insert_first(elem))
+    }
+    /// Insert a new element at the end, but only if that can be done without
+    /// allocating memory.
+    ///
+    /// Returns the new index, or the element back as an error when there is
+    /// neither a free index to reuse nor any spare capacity.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::with_capacity(2);
+    /// list.insert_last(1);
+    /// list.insert_last(2);
+    /// assert_eq!(list.try_insert_last_within_capacity(3), Err(3));
+    /// list.remove_first();
+    /// assert!(list.try_insert_last_within_capacity(3).is_ok());
+    /// assert_eq!(list.to_string(), "[2 >< 3]");
+    /// ```
+    pub fn try_insert_last_within_capacity(&mut self, elem: T) -> Result<ListIndex, T> {
+        if !self.has_spare_capacity() {
+            return Err(elem);
+        }
+        Ok(self.insert_last(elem))
+    }
+    /// Insert a new element before the index, like `insert_before`, but only
+    /// if that can be done without allocating memory.
+    ///
+    /// Returns the new index, or the element back as an error when there is
+    /// neither a free index to reuse nor any spare capacity.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::with_capacity(2);
+    /// let index = list.insert_last(2);
+    /// assert!(list.try_insert_before_within_capacity(index, 1).is_ok());
+    /// assert_eq!(list.try_insert_before_within_capacity(index, 0), Err(0));
+    /// ```
+    pub fn try_insert_before_within_capacity(&mut self, index: ListIndex, elem: T)
+        -> Result<ListIndex, T>
+    {
+        if !self.has_spare_capacity() {
+            return Err(elem);
+        }
+        Ok(self.insert_before(index, elem))
+    }
+    /// Insert a new element after the index, like `insert_after`, but only if
+    /// that can be done without allocating memory.
+    ///
+    /// Returns the new index, or the element back as an error when there is
+    /// neither a free index to reuse nor any spare capacity.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::with_capacity(2);
+    /// let index = list.insert_last(1);
+    /// assert!(list.try_insert_after_within_capacity(index, 2).is_ok());
+    /// assert_eq!(list.try_insert_after_within_capacity(index, 3), Err(3));
+    /// ```
+    pub fn try_insert_after_within_capacity(&mut self, index: ListIndex, elem: T)
+        -> Result<ListIndex, T>
+    {
+        if !self.has_spare_capacity() {
+            return Err(elem);
+        }
+        Ok(self.insert_after(index, elem))
+    }
+    /// Take an empty slot out of the list, reusing a free index when there
+    /// is one and growing the capacity otherwise, and return its index.
+    ///
+    /// The slot holds no data and is not part of the list, but its index
+    /// will not be handed out again until it is given back with
+    /// `release_slot`, so companion arrays indexed by the same index can be
+    /// allocated and freed in step with the list.
+    ///
+    /// Trimming the list keeps the slots that have not been released, while
+    /// `clear` takes them all back, after which `release_slot` returns
+    /// `false` for them.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 2]);
+    /// let index = list.first_index();
+    /// list.remove(index);
+    /// assert_eq!(list.acquire_free_slot(), index);
+    /// assert!(!list.is_index_used(index));
+    /// assert_ne!(list.insert_last(3), index);
+    /// assert!(list.release_slot(index));
+    /// assert_eq!(list.insert_last(4), index);
+    /// ```
+    pub fn acquire_free_slot(&mut self) -> ListIndex {
+        let reuse = self.free.head;
+        if reuse.is_some() {
+            self.linkout_free(reuse);
+            return reuse;
+        }
+        let pos = self.nodes.len();
+        self.nodes.push(ListNode::new());
+        self.bits.reserve(self.nodes.capacity());
+        self.bits.push(false);
+        self.elems.push(None);
+        ListIndex::from(pos)
+    }
+    /// Give back a slot taken with `acquire_free_slot`, so that its index
+    /// can be reused by the list.
+    ///
+    /// Returns `false`, and does nothing, if the index is not a slot that
+    /// was taken out of the list.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::<u32>::new();
+    /// let index = list.acquire_free_slot();
+    /// assert!(list.release_slot(index));
+    /// assert!(!list.release_slot(index));
+    /// ```
+    pub fn release_slot(&mut self, index: ListIndex) -> bool {
+        match index.get() {
+            Some(at) if at < self.capacity() && self.is_detached(at) => {
+                self.linkin_free(index);
+                true
+            }
+            _ => false,
+        }
+    }
+    /// Insert a new element at the beginning, unless an equal element is
+    /// already in the list.
+    ///
+    /// Returns the index of the new element, or the index of the first
+    /// existing equal element in which case nothing is inserted.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![2, 3]);
+    /// let index = list.insert_first_unique(3);
+    /// assert_eq!(index, list.last_index());
+    /// list.insert_first_unique(1);
+    /// assert_eq!(list.to_string(), "[1 >< 2 >< 3]");
+    /// ```
+    pub fn insert_first_unique(&mut self, elem: T) -> ListIndex
+    where
+        T: PartialEq,
+    {
+        let found = self.find_index(|e| e == &elem);
+        if found.is_some() {
+            return found;
+        }
+        self.insert_first(elem)
+    }
+    /// Insert a new element at the end, unless an equal element is already
+    /// in the list.
+    ///
+    /// Returns the index of the new element, or the index of the first
+    /// existing equal element in which case nothing is inserted.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 2]);
+    /// let index = list.insert_last_unique(1);
+    /// assert_eq!(index, list.first_index());
+    /// list.insert_last_unique(3);
+    /// assert_eq!(list.to_string(), "[1 >< 2 >< 3]");
+    /// ```
+    pub fn insert_last_unique(&mut self, elem: T) -> ListIndex
+    where
+        T: PartialEq,
+    {
+        let found = self.find_index(|e| e == &elem);
+        if found.is_some() {
+            return found;
+        }
+        self.insert_last(elem)
+    }
+    /// Insert a new element at the beginning, unless the predicate matches an
+    /// element already in the list.
+    ///
+    /// Returns the index of the new element, or the index of the first
+    /// matching element in which case nothing is inserted.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![(1, "a"), (2, "b")]);
+    /// list.insert_first_unique_by((2, "c"), |e| e.0 == 2);
+    /// list.insert_first_unique_by((0, "d"), |e| e.0 == 0);
+    /// assert_eq!(list.len(), 3);
+    /// assert_eq!(list.get_first(), Some(&(0, "d")));
+    /// ```
+    pub fn insert_first_unique_by<F>(&mut self, elem: T, pred: F) -> ListIndex
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let found = self.find_index(pred);
+        if found.is_some() {
+            return found;
+        }
+        self.insert_first(elem)
+    }
+    /// Insert a new element at the end, unless the predicate matches an
+    /// element already in the list.
+    ///
+    /// Returns the index of the new element, or the index of the first
+    /// matching element in which case nothing is inserted.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![(1, "a"), (2, "b")]);
+    /// list.insert_last_unique_by((1, "c"), |e| e.0 == 1);
+    /// list.insert_last_unique_by((3, "d"), |e| e.0 == 3);
+    /// assert_eq!(list.len(), 3);
+    /// assert_eq!(list.get_last(), Some(&(3, "d")));
+    /// ```
+    pub fn insert_last_unique_by<F>(&mut self, elem: T, pred: F) -> ListIndex
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let found = self.find_index(pred);
+        if found.is_some() {
+            return found;
+        }
+        self.insert_last(elem)
+    }
+    /// Insert a clone of the separator between every pair of adjacent
+    /// elements, in a single pass.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec!["a", "b", "c"]);
+    /// list.intersperse(",");
+    /// assert_eq!(list.to_string(), "[a >< , >< b >< , >< c]");
+    /// ```
+    pub fn intersperse(&mut self, sep: T)
+    where
+        T: Clone,
+    {
+        self.intersperse_with(|| sep.clone())
+    }
+    /// Insert a separator made by the closure between every pair of adjacent
+    /// elements, in a single pass.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 2, 3]);
+    /// let mut n = 0;
+    /// list.intersperse_with(|| { n -= 1; n });
+    /// assert_eq!(list.to_string(), "[1 >< -1 >< 2 >< -2 >< 3]");
+    /// ```
+    pub fn intersperse_with<F>(&mut self, mut sep: F)
+    where
+        F: FnMut() -> T,
+    {
+        let mut index = self.first_index();
+        while index.is_some() {
+            let next = self.next_index(index);
+            if next.is_some() {
+                self.insert_after(index, sep());
+            }
+            index = next;
+        }
+    }
+    /// Remove the first element and return its data.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// # let mut list = IndexList::<u64>::new();
+    /// # list.insert_first(42);
+    /// let data = list.remove_first();
+    /// # assert_eq!(data, Some(42));
+    /// ```
+    pub fn remove_first(&mut self) -> Option<T> {
+        self.remove(self.first_index())
+    }
+    /// Remove the last element and return its data.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// # let mut list = IndexList::<u64>::new();
+    /// # list.insert_last(42);
+    /// let data = list.remove_last();
+    /// # assert_eq!(data, Some(42));
+    /// ```
+    pub fn remove_last(&mut self) -> Option<T> {
+        self.remove(self.last_index())
+    }
+    /// Remove the first element and return its data, but only if the
+    /// predicate returns `true` for it.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut expiry = IndexList::from(&mut vec![5, 10, 15]);
+    /// let now = 7;
+    /// assert_eq!(expiry.pop_first_if(|&t| t <= now), Some(5));
+    /// assert_eq!(expiry.pop_first_if(|&t| t <= now), None);
+    /// ```
+    pub fn pop_first_if<F>(&mut self, pred: F) -> Option<T>
+    where
+        F: FnOnce(&T) -> bool,
+    {
+        self.get_first().filter(|&elem| pred(elem))?;
+        self.remove_first()
+    }
+    /// Remove the last element and return its data, but only if the
+    /// predicate returns `true` for it.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 2]);
+    /// assert_eq!(list.pop_last_if(|&n| n % 2 == 1), None);
+    /// assert_eq!(list.pop_last_if(|&n| n % 2 == 0), Some(2));
+    /// ```
+    pub fn pop_last_if<F>(&mut self, pred: F) -> Option<T>
+    where
+        F: FnOnce(&T) -> bool,
+    {
+        self.get_last().filter(|&elem| pred(elem))?;
+        self.remove_last()
+    }
+    /// Keep only the first `len` elements, in list order, and drop the
+    /// rest. Nothing happens if the list is not longer than `len`.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 2, 3, 4]);
+    /// list.truncate(2);
+    /// assert_eq!(list.to_string(), "[1 >< 2]");
+    /// list.truncate(5);
+    /// assert_eq!(list.len(), 2);
+    /// ```
+    pub fn truncate(&mut self, len: usize) {
+        while self.size > len {
+            self.remove_last();
+        }
+    }
+    /// Change the length of the list to `new_len`, by adding clones of
+    /// `value` at the end or by dropping elements from the end.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut window = IndexList::from(&mut vec![1, 2, 3]);
+    /// window.resize(5, 0);
+    /// assert_eq!(window.to_string(), "[1 >< 2 >< 3 >< 0 >< 0]");
+    /// window.resize(2, 0);
+    /// assert_eq!(window.to_string(), "[1 >< 2]");
+    /// ```
+    pub fn resize(&mut self, new_len: usize, value: T)
+    where
+        T: Clone,
+    {
+        self.resize_with(new_len, || value.clone());
+    }
+    /// Change the length of the list to `new_len`, by adding elements made by
+    /// the closure at the end or by dropping elements from the end.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::new();
+    /// let mut next = 0;
+    /// list.resize_with(3, || { next += 1; next });
+    /// assert_eq!(list.to_string(), "[1 >< 2 >< 3]");
+    /// ```
+    pub fn resize_with<F>(&mut self, new_len: usize, mut f: F)
+    where
+        F: FnMut() -> T,
+    {
+        self.truncate(new_len);
+        while self.size < new_len {
+            self.insert_last(f());
+        }
+    }
+    /// Drop every element after the index, which becomes the last element.
+    ///
+    /// Returns the number of elements that were dropped, which is zero if
+    /// the index is not valid.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut history = IndexList::from(&mut vec![1, 2, 3, 4]);
+    /// let checkpoint = history.next_index(history.first_index());
+    /// assert_eq!(history.truncate_after(checkpoint), 2);
+    /// assert_eq!(history.to_string(), "[1 >< 2]");
+    /// ```
+    pub fn truncate_after(&mut self, index: ListIndex) -> usize {
+        if !self.is_index_used(index) {
+            return 0;
+        }
+        let mut dropped = 0;
+        while self.last_index() != index {
+            self.remove_last();
+            dropped += 1;
+        }
+        dropped
+    }
+    /// Drop every element before the index, which becomes the first element.
+    ///
+    /// Returns the number of elements that were dropped, which is zero if
+    /// the index is not valid.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut history = IndexList::from(&mut vec![1, 2, 3, 4]);
+    /// let checkpoint = history.prev_index(history.last_index());
+    /// assert_eq!(history.truncate_before(checkpoint), 2);
+    /// assert_eq!(history.to_string(), "[3 >< 4]");
+    /// ```
+    pub fn truncate_before(&mut self, index: ListIndex) -> usize {
+        if !self.is_index_used(index) {
+            return 0;
+        }
+        let mut dropped = 0;
+        while self.first_index() != index {
+            self.remove_first();
+            dropped += 1;
+        }
+        dropped
+    }
+    /// Remove the element at the index and return its data.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// # let mut list = IndexList::from(&mut vec!["A", "B", "C"]);
+    /// # let mut index = list.first_index();
+    /// # index = list.next_index(index);
+    /// let data = list.remove(index);
+    /// # assert_eq!(data, Some("B"));
+    /// ```
+    pub fn remove(&mut self, index: ListIndex) -> Option<T> {
+        let elem_opt = self.remove_elem_at_index(index);
+        if elem_opt.is_some() {
+            self.linkout_used(index);
+            self.linkin_free(index);
+        }
+        elem_opt
+    }
+    /// Remove the element at the index and return its data, but only if the
+    /// predicate returns `true` for it.
+    ///
+    /// Returns `None` if the index is not valid or the predicate returns
+    /// `false`, and then the list is unchanged.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec!["keep", "drop"]);
+    /// let index = list.last_index();
+    /// assert_eq!(list.remove_if(list.first_index(), |s| *s == "drop"), None);
+    /// assert_eq!(list.remove_if(index, |s| *s == "drop"), Some("drop"));
+    /// assert_eq!(list.remove_if(index, |_| true), None);
+    /// ```
+    pub fn remove_if<F>(&mut self, index: ListIndex, pred: F) -> Option<T>
+    where
+        F: FnOnce(&T) -> bool,
+    {
+        self.get(index).filter(|&elem| pred(elem))?;
+        self.remove(index)
+    }
+    /// Remove the elements at all the indexes, skipping any index that is
+    /// not valid, or that was already removed.
+    ///
+    /// Returns the number of elements that were removed.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::{IndexList, ListIndex};
+    /// let mut list = IndexList::from(&mut vec![1, 2, 3, 4]);
+    /// let (first, last) = (list.first_index(), list.last_index());
+    /// assert_eq!(list.remove_many(&[first, last, first, ListIndex::new()]), 2);
+    /// assert_eq!(list.to_string(), "[2 >< 3]");
+    /// ```
+    pub fn remove_many(&mut self, indexes: &[ListIndex]) -> usize {
+        indexes.iter().filter(|&&index| self.remove(index).is_some()).count()
+    }
+    /// Remove the first element, in list order, that is equal to `elem`.
+    ///
+    /// Returns the index the element had together with its data, or `None`
+    /// if no equal element was found.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec!["A", "B", "C", "B"]);
+    /// let index = list.next_index(list.first_index());
+    /// assert_eq!(list.remove_item(&"B"), Some((index, "B")));
+    /// assert_eq!(list.to_string(), "[A >< C >< B]");
+    /// assert_eq!(list.remove_item(&"D"), None);
+    /// ```
+    pub fn remove_item(&mut self, elem: &T) -> Option<(ListIndex, T)>
+    where
+        T: PartialEq,
+    {
+        let index = self.find_index(|e| e == elem);
+        Some((index, self.remove(index)?))
+    }
+    /// Remove every element that is equal to `elem`, in a single pass.
+    ///
+    /// Returns the number of elements that were removed.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 2, 1, 3, 1]);
+    /// assert_eq!(list.remove_all(&1), 3);
+    /// assert_eq!(list.to_string(), "[2 >< 3]");
+    /// ```
+    pub fn remove_all(&mut self, elem: &T) -> usize
+    where
+        T: PartialEq,
+    {
+        self.remove_matching(|e| e == elem)
+    }
+    /// Remove every element for which the predicate returns `true`, in a
+    /// single pass, which is the opposite of `retain`.
+    ///
+    /// Returns the number of elements that were removed.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut queue = IndexList::from(&mut vec![Ok(1), Err("x"), Ok(2), Err("y")]);
+    /// assert_eq!(queue.remove_all_by(|job| job.is_err()), 2);
+    /// assert_eq!(queue.len(), 2);
+    /// ```
+    pub fn remove_all_by<F>(&mut self, pred: F) -> usize
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.remove_matching(pred)
+    }
+    /// Keep only the elements for which the predicate returns `true`, and
+    /// remove the rest in a single pass, in list order.
+    ///
+    /// The elements that are kept keep their indexes.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 2, 3, 4, 5]);
+    /// let last = list.last_index();
+    /// list.retain(|&n| n % 2 == 1);
+    /// assert_eq!(list.to_string(), "[1 >< 3 >< 5]");
+    /// assert_eq!(list.get(last), Some(&5));
+    /// ```
+    pub fn retain<F>(&mut self, mut pred: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.remove_matching(|elem| !pred(elem));
+    }
+    /// Keep only the elements for which the predicate returns `true`, like
+    /// `retain`, but with mutable access so that the elements can also be
+    /// changed in the same pass.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut ttl = IndexList::from(&mut vec![1, 3, 2]);
+    /// ttl.retain_mut(|left| {
+    ///     *left -= 1;
+    ///     *left > 0
+    /// });
+    /// assert_eq!(ttl.to_string(), "[2 >< 1]");
+    /// ```
+    pub fn retain_mut<F>(&mut self, mut pred: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let mut index = self.first_index();
+        while let Some(elem) = self.get_mut(index) {
+            let keep = pred(elem);
+            let next = self.next_index(index);
+            if !keep {
+                self.remove(index);
+            }
+            index = next;
+        }
+    }
+    /// Remove every element that is equal to an element earlier in the list,
+    /// wherever it is, so that only the first occurrence of each is kept,
+    /// with its index.
+    ///
+    /// Returns the number of elements that were removed.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut jobs = IndexList::from(&mut vec![7, 3, 7, 1, 3]);
+    /// let first = jobs.first_index();
+    /// assert_eq!(jobs.dedup_global(), 2);
+    /// assert_eq!(jobs.to_string(), "[7 >< 3 >< 1]");
+    /// assert_eq!(jobs.get(first), Some(&7));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn dedup_global(&mut self) -> usize
+    where
+        T: Eq + Hash,
+    {
+        let mut seen = HashSet::with_capacity(self.len());
+        let mut later = Vec::new();
+        let mut index = self.first_index();
+        while let Some(elem) = self.get(index) {
+            if !seen.insert(elem) {
+                later.push(index);
+            }
+            index = self.next_index(index);
+        }
+        later.iter().for_each(|&index| { self.remove(index); });
+        later.len()
+    }
+    /// Create a new iterator over all the elements.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// # let mut list = IndexList::from(&mut vec![120, 240, 360]);
+    /// let total: usize = list.iter().sum();
+    /// assert_eq!(total, 720);
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> ListIter<'_, T> {
+        ListIter {
+            list: self,
+            next: self.first_index(),
+            prev: self.last_index(),
+            len: self.len(),
+        }
+    }
+    /// Create a new iterator over all the elements, as if the list started at
+    /// the index `start`, wrapping around from the end of the list to the
+    /// beginning. The list is not changed.
+    ///
+    /// If `start` is not a valid index the iterator is empty.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let list = IndexList::from(&mut vec![1, 2, 3, 4]);
+    /// let start = list.get_nth(2);
+    /// let turn: Vec<_> = list.iter_rotated(start).collect();
+    /// assert_eq!(turn, vec![&3, &4, &1, &2]);
+    /// let back: Vec<_> = list.iter_rotated(start).rev().collect();
+    /// assert_eq!(back, vec![&2, &1, &4, &3]);
+    /// ```
+    #[inline]
+    pub fn iter_rotated(&self, start: ListIndex) -> ListIter<'_, T> {
+        let valid = self.is_index_used(start);
+        ListIter {
+            list: self,
+            next: start,
+            prev: self.prev_index(start),
+            len: if valid { self.len() } else { 0 },
+        }
+    }
+    /// Returns a string of all the elements, in order, with the separator
+    /// between them.
+    ///
+    /// The elements are formatted straight into the string, without any
+    /// intermediate strings.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let list = IndexList::from(&mut vec!["Hello,", "world!"]);
+    /// assert_eq!(list.join(" "), "Hello, world!");
+    /// assert_eq!(IndexList::from(&mut vec![1, 2, 3]).join(", "), "1, 2, 3");
+    /// ```
+    pub fn join(&self, sep: &str) -> String
+    where
+        T: fmt::Display,
+    {
+        let mut out = String::new();
+        // writing to a string can not fail
+        let _ = self.write_joined(&mut out, sep);
+        out
+    }
+    /// Create a new iterator that does not borrow the list, so that the list
+    /// can be changed while it is used, see `ResumableIter`.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 2, 3]);
+    /// let mut scan = list.resumable_iter();
+    /// while let Some((index, _)) = scan.next(&list) {
+    ///     list.update(index, |data| *data *= 10);
+    /// }
+    /// assert_eq!(list.to_string(), "[10 >< 20 >< 30]");
+    /// ```
+    #[inline]
+    pub fn resumable_iter(&self) -> ResumableIter {
+        ResumableIter::new(self)
+    }
+    /// Create a new iterator that does not borrow the list, but that fails
+    /// once the structure of the list is changed, see `CheckedIter`.
+    ///
+    /// Only available with the `fail-fast` feature.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 2, 3]);
+    /// let mut scan = list.iter_checked();
+    /// while let Ok(Some((index, _))) = scan.next(&list) {
+    ///     list.update(index, |data| *data *= 10);
+    /// }
+    /// assert_eq!(list.to_string(), "[10 >< 20 >< 30]");
+    /// ```
+    #[cfg(feature = "fail-fast")]
+    #[inline]
+    pub fn iter_checked(&self) -> CheckedIter {
+        CheckedIter::new(self)
+    }
+    /// Create a cursor that starts at the first element.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let list = IndexList::from(&mut vec![1, 2]);
+    /// let cursor = list.cursor_front();
+    /// assert_eq!(cursor.index(), list.first_index());
+    /// ```
+    #[inline]
+    pub fn cursor_front(&self) -> Cursor<'_, T> {
+        Cursor::new(self, self.first_index())
+    }
+    /// Create a cursor that starts at the last element.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let list = IndexList::from(&mut vec![1, 2]);
+    /// let mut cursor = list.cursor_back();
+    /// assert_eq!(cursor.current(), Some(&2));
+    /// cursor.move_prev();
+    /// assert_eq!(cursor.current(), Some(&1));
+    /// ```
+    #[inline]
+    pub fn cursor_back(&self) -> Cursor<'_, T> {
+        Cursor::new(self, self.last_index())
+    }
+    /// Create a cursor that starts at the index, or between the last and the
+    /// first element if the index is not valid.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::{IndexList, ListIndex};
+    /// let list = IndexList::from(&mut vec![1, 2, 3]);
+    /// let index = list.next_index(list.first_index());
+    /// assert_eq!(list.cursor_at(index).current(), Some(&2));
+    /// assert_eq!(list.cursor_at(ListIndex::new()).current(), None);
+    /// ```
+    #[inline]
+    pub fn cursor_at(&self, index: ListIndex) -> Cursor<'_, T> {
+        Cursor::new(self, index)
+    }
+    /// Create a cursor that can change the list, starting at the first
+    /// element.
+    #[inline]
+    pub fn cursor_mut_front(&mut self) -> CursorMut<'_, T> {
+        let index = self.first_index();
+        CursorMut::new(self, index)
+    }
+    /// Create a cursor that can change the list, starting at the last
+    /// element.
+    #[inline]
+    pub fn cursor_mut_back(&mut self) -> CursorMut<'_, T> {
+        let index = self.last_index();
+        CursorMut::new(self, index)
+    }
+    /// Create a cursor that can change the list, starting at the index, or
+    /// between the last and the first element if the index is not valid.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 2, 3]);
+    /// let index = list.next_index(list.first_index());
+    /// let mut cursor = list.cursor_mut_at(index);
+    /// assert_eq!(cursor.remove_current(), Some(2));
+    /// assert_eq!(cursor.current(), Some(&mut 3));
+    /// cursor.insert_before(4);
+    /// assert_eq!(list.to_string(), "[1 >< 4 >< 3]");
+    /// ```
+    #[inline]
+    pub fn cursor_mut_at(&mut self, index: ListIndex) -> CursorMut<'_, T> {
+        CursorMut::new(self, index)
+    }
+    /// Returns the first element data and an iterator over the rest of the
+    /// elements, or `None` if the list is empty.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let list = IndexList::from(&mut vec![1, 2, 3]);
+    /// if let Some((first, rest)) = list.split_first() {
+    ///     assert_eq!(*first, 1);
+    ///     assert_eq!(rest.rev().collect::<Vec<_>>(), vec![&3, &2]);
+    /// }
+    /// ```
+    pub fn split_first(&self) -> Option<(&T, ListIter<'_, T>)> {
+        let first = self.get_first()?;
+        let rest = ListIter {
+            list: self,
+            next: self.next_index(self.first_index()),
+            prev: self.last_index(),
+            len: self.len() - 1,
+        };
+        Some((first, rest))
+    }
+    /// Returns the last element data and an iterator over the rest of the
+    /// elements, or `None` if the list is empty.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let list = IndexList::from(&mut vec![1, 2, 3]);
+    /// if let Some((last, rest)) = list.split_last() {
+    ///     assert_eq!(*last, 3);
+    ///     assert_eq!(rest.collect::<Vec<_>>(), vec![&1, &2]);
+    /// }
+    /// ```
+    pub fn split_last(&self) -> Option<(&T, ListIter<'_, T>)> {
+        let last = self.get_last()?;
+        let rest = ListIter {
+            list: self,
+            next: self.first_index(),
+            prev: self.prev_index(self.last_index()),
+            len: self.len() - 1,
+        };
+        Some((last, rest))
+    }
+    /// Create a draining iterator over all the elements.
+    ///
+    /// This iterator will remove the elements as it is iterating over them.
+    ///
+    /// Only the elements that are actually iterated over are removed. If the
+    /// iterator is dropped early then the remaining elements are left in the
+    /// list, with their indexes intact.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// # let mut list = IndexList::from(&mut vec!["A", "B", "C"]);
+    /// let items: Vec<&str> = list.drain_iter().collect();
+    /// assert_eq!(list.len(), 0);
+    /// assert_eq!(items, vec!["A", "B", "C"]);
+    /// ```
+    ///
+    /// Consuming at most a number of elements:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec!["A", "B", "C", "D"]);
+    /// let index = list.last_index();
+    /// let batch: Vec<&str> = list.drain_iter().take(2).collect();
+    /// assert_eq!(batch, vec!["A", "B"]);
+    /// assert_eq!(list.to_string(), "[C >< D]");
+    /// assert_eq!(list.get(index), Some(&"D"));
+    /// ```
+    #[inline]
+    pub fn drain_iter(&mut self) -> ListDrainIter<'_, T> {
+        ListDrainIter::new(self)
+    }
+    /// Create a draining iterator over all the elements that also yields the
+    /// index each element had.
+    ///
+    /// This iterator will remove the elements as it is iterating over them.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// # use std::collections::HashMap;
+    /// let mut list = IndexList::new();
+    /// let mut names = HashMap::new();
+    /// names.insert(list.insert_last("A"), "a");
+    /// names.insert(list.insert_last("B"), "b");
+    /// for (index, _elem) in list.drain_entries() {
+    ///     names.remove(&index);
+    /// }
+    /// assert!(list.is_empty());
+    /// assert!(names.is_empty());
+    /// ```
+    #[inline]
+    pub fn drain_entries(&mut self) -> ListDrainEntries<'_, T> {
+        ListDrainEntries::new(self)
+    }
+    /// Move all the elements, in order, into an existing collection, such as a
+    /// `Vec`, leaving the list empty.
+    ///
+    /// This reuses whatever capacity the collection already has, and all
+    /// indexes are invalidated afterwards. The elements are removed one at a
+    /// time, as with `drain_iter`, so if the collection panics the elements
+    /// not yet moved remain in the list.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 2, 3]);
+    /// let mut scratch = Vec::with_capacity(8);
+    /// scratch.push(0);
+    /// list.drain_into(&mut scratch);
+    /// assert!(list.is_empty());
+    /// assert_eq!(scratch, vec![0, 1, 2, 3]);
+    /// ```
+    pub fn drain_into<E>(&mut self, out: &mut E)
+    where
+        E: Extend<T>,
+    {
+        out.extend(self.drain_iter());
+    }
+    /// Replace all the elements with the new ones, in order, and return the
+    /// old elements, in order.
+    ///
+    /// The memory of the list is reused, and the new elements get the
+    /// indexes from the start of the vector. All the old indexes are
+    /// invalidated, as with `clear`.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut work = IndexList::from(&mut vec![1, 2, 3]);
+    /// let done = work.replace_contents(vec![4, 5]);
+    /// assert_eq!(done, vec![1, 2, 3]);
+    /// assert_eq!(work.to_string(), "[4 >< 5]");
+    /// assert_eq!(work.capacity(), 2);
+    /// ```
+    pub fn replace_contents(&mut self, new: Vec<T>) -> Vec<T> {
+        let mut old = Vec::with_capacity(self.len());
+        self.drain_into(&mut old);
+        self.clear();
+        self.extend(new);
+        old
+    }
+    /// Consume the list and return its elements in a sorted vector.
+    ///
+    /// The elements are taken straight from the element vector, without
+    /// walking the list, and then sorted, so elements that are equal come in
+    /// no particular order.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![3, 1, 2]);
+    /// list.insert_first(0);
+    /// assert_eq!(list.into_sorted_vec(), vec![0, 1, 2, 3]);
+    /// ```
+    pub fn into_sorted_vec(self) -> Vec<T>
+    where
+        T: Ord,
+    {
+        let mut elems: Vec<T> = self.elems.into_iter().flatten().collect();
+        elems.sort_unstable();
+        elems
+    }
+    /// Create a draining iterator that removes elements from the front of the
+    /// list for as long as the predicate holds.
+    ///
+    /// The iteration stops at the first element for which the predicate
+    /// returns `false`, which is left in the list together with all the
+    /// elements after it.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut timers = IndexList::from(&mut vec![10, 20, 30, 40]);
+    /// let now = 25;
+    /// let expired: Vec<u32> = timers.drain_while(|&t| t <= now).collect();
+    /// assert_eq!(expired, vec![10, 20]);
+    /// assert_eq!(timers.to_string(), "[30 >< 40]");
+    /// ```
+    #[inline]
+    pub fn drain_while<F>(&mut self, pred: F) -> ListDrainWhile<'_, T, F>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        ListDrainWhile::new(self, pred)
     }
-    /// Remove the element at the index and return its data.
+    /// Create a draining iterator that removes the elements from `start` up
+    /// to, but not including, `end`.
+    ///
+    /// If `end` is `None`, or is not found after `start`, then the elements
+    /// are removed up to the end of the list. Nothing is removed if `start`
+    /// is not a valid index.
     ///
     /// Example:
     /// ```rust
-    /// # use index_list::IndexList;
-    /// # let mut list = IndexList::from(&mut vec!["A", "B", "C"]);
-    /// # let mut index = list.first_index();
-    /// # index = list.next_index(index);
-    /// let data = list.remove(index);
-    /// # assert_eq!(data, Some("B"));
+    /// # use index_list::{IndexList, ListIndex};
+    /// let mut list = IndexList::from(&mut vec![1, 2, 3, 4, 5]);
+    /// let start = list.next_index(list.first_index());
+    /// let end = list.last_index();
+    /// assert_eq!(list.drain_range(start, end).collect::<Vec<_>>(), vec![2, 3, 4]);
+    /// assert_eq!(list.to_string(), "[1 >< 5]");
+    /// let start = list.last_index();
+    /// assert_eq!(list.drain_range(start, ListIndex::new()).count(), 1);
     /// ```
-    pub fn remove(&mut self, index: ListIndex) -> Option<T> {
-        let elem_opt = self.remove_elem_at_index(index);
-        if elem_opt.is_some() {
-            self.linkout_used(index);
-            self.linkin_free(index);
-        }
-        elem_opt
+    #[inline]
+    pub fn drain_range(&mut self, start: ListIndex, end: ListIndex) -> ListDrainRange<'_, T> {
+        ListDrainRange::new(self, start, end)
     }
-    /// Create a new iterator over all the elements.
+    /// Create a draining iterator that removes the elements from `start` up
+    /// to and including `end`, see `drain_range`.
     ///
     /// Example:
     /// ```rust
     /// # use index_list::IndexList;
-    /// # let mut list = IndexList::from(&mut vec![120, 240, 360]);
-    /// let total: usize = list.iter().sum();
-    /// assert_eq!(total, 720);
+    /// let mut list = IndexList::from(&mut vec![1, 2, 3, 4]);
+    /// let start = list.first_index();
+    /// let end = list.next_index(start);
+    /// let drained: Vec<_> = list.drain_range_inclusive(start, end).collect();
+    /// assert_eq!(drained, vec![1, 2]);
+    /// assert_eq!(list.to_string(), "[3 >< 4]");
     /// ```
     #[inline]
-    pub fn iter(&self) -> ListIter<T> {
-        ListIter {
-            list: self,
-            next: self.first_index(),
-            prev: self.last_index(),
-        }
+    pub fn drain_range_inclusive(&mut self, start: ListIndex, end: ListIndex)
+        -> ListDrainRange<'_, T>
+    {
+        let end = if self.is_index_used(end) { self.next_index(end) } else { ListIndex::new() };
+        ListDrainRange::new(self, start, end)
     }
-    /// Create a draining iterator over all the elements.
+    /// Create an iterator that removes and returns the elements for which
+    /// the predicate returns `true`, walking the list from the front.
     ///
-    /// This iterator will remove the elements as it is iterating over them.
+    /// The predicate may change the elements, and the elements that are not
+    /// removed keep their indexes. If the iterator is dropped early, the
+    /// rest of the list is left as it is.
     ///
     /// Example:
     /// ```rust
     /// # use index_list::IndexList;
-    /// # let mut list = IndexList::from(&mut vec!["A", "B", "C"]);
-    /// let items: Vec<&str> = list.drain_iter().collect();
-    /// assert_eq!(list.len(), 0);
-    /// assert_eq!(items, vec!["A", "B", "C"]);
+    /// let mut jobs = IndexList::from(&mut vec![("a", 0), ("b", 3), ("c", 0)]);
+    /// let live = jobs.next_index(jobs.first_index());
+    /// let expired: Vec<_> = jobs.extract_if(|job| job.1 == 0).collect();
+    /// assert_eq!(expired, vec![("a", 0), ("c", 0)]);
+    /// assert_eq!(jobs.get(live), Some(&("b", 3)));
+    /// assert_eq!(jobs.len(), 1);
     /// ```
     #[inline]
-    pub fn drain_iter(&mut self) -> ListDrainIter<T> {
-        ListDrainIter::new(self)
+    pub fn extract_if<F>(&mut self, pred: F) -> ListExtractIf<'_, T, F>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        ListExtractIf::new(self, pred)
     }
     /// Create a vector for all elements.
     ///
@@ -736,6 +2873,34 @@ impl<T> IndexList<T> {
     pub fn to_vec(&self) -> Vec<&T> {
         self.iter().filter_map(Option::Some).collect()
     }
+    /// Apply a function to each pair of elements at the same position in
+    /// both lists.
+    ///
+    /// The lists are walked in order, side by side, and the walk stops when
+    /// the end of either list is reached.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 2, 3]);
+    /// let deltas = IndexList::from(&mut vec![10, 20]);
+    /// list.zip_apply(&deltas, |elem, delta| *elem += delta);
+    /// assert_eq!(list.to_string(), "[11 >< 22 >< 3]");
+    /// ```
+    pub fn zip_apply<U, F>(&mut self, other: &IndexList<U>, mut f: F)
+    where
+        F: FnMut(&mut T, &U),
+    {
+        let mut this = self.first_index();
+        let mut that = other.first_index();
+        while this.is_some() && that.is_some() {
+            if let (Some(elem), Some(data)) = (self.get_mut(this), other.get(that)) {
+                f(elem, data);
+            }
+            this = self.next_index(this);
+            that = other.next_index(that);
+        }
+    }
     /// Insert all the elements from the vector, which will be drained.
     ///
     /// Example:
@@ -753,11 +2918,43 @@ impl<T> IndexList<T> {
         });
         list
     }
+    /// Combine all the lists, in order, into a single new list.
+    ///
+    /// The capacity for all elements is reserved up front and every element
+    /// is linked in only once.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let shards = vec![
+    ///     IndexList::from(&mut vec![1, 2]),
+    ///     IndexList::new(),
+    ///     IndexList::from(&mut vec![3, 4, 5]),
+    /// ];
+    /// let list = IndexList::concat(shards);
+    /// assert_eq!(list.capacity(), 5);
+    /// assert_eq!(list.to_string(), "[1 >< 2 >< 3 >< 4 >< 5]");
+    /// ```
+    pub fn concat(lists: Vec<IndexList<T>>) -> IndexList<T> {
+        let total = lists.iter().map(|other| other.len()).sum();
+        let mut list = IndexList::<T>::with_capacity(total);
+        for mut other in lists {
+            let mut index = other.first_index();
+            while let Some(at) = index.get() {
+                if let Some(elem) = other.elems.get_mut(at).and_then(Option::take) {
+                    list.insert_last(elem);
+                }
+                index = other.next_index(index);
+            }
+        }
+        list
+    }
     /// Remove any unused indexes at the end by truncating.
     ///
     /// If the unused indexes don't appear at the end, then nothing happens.
     ///
-    /// No valid indexes are changed.
+    /// No valid indexes are changed, and slots taken with `acquire_free_slot`
+    /// are kept.
     ///
     /// Example:
     /// ```rust
@@ -769,23 +2966,28 @@ impl<T> IndexList<T> {
     /// assert_eq!(list.len(), list.capacity());
     /// ```
     pub fn trim_safe(&mut self) {
-        let removed: Vec<usize> = (self.len()..self.capacity())
+        let used = self.bits.used_len();
+        // slots taken with acquire_free_slot are kept, like the used ones
+        let left = (used..self.capacity())
             .rev()
-            .take_while(|&i| self.is_free(i))
-            .collect();
-        removed.iter().for_each(|&i| {
-            self.linkout_free(ListIndex::from(i));
+            .find(|&at| self.is_detached(at))
+            .map_or(used, |at| at + 1);
+        (left..self.capacity()).for_each(|i| {
+            if !self.is_detached(i) {
+                self.linkout_free(ListIndex::from(i));
+            }
         });
-        if !removed.is_empty() {
-            let left = self.capacity() - removed.len();
-            self.nodes.truncate(left);
-            self.elems.truncate(left);
-        }
+        self.nodes.truncate(left);
+        self.elems.truncate(left);
+        self.bits.truncate(left);
+        self.tags.truncate(left);
     }
     /// Remove all unused elements by swapping indexes and then truncating.
     ///
     /// This will reduce the capacity of the list, but only if there are any
-    /// unused elements. Length and capacity will be equal after the call.
+    /// unused elements. Length and capacity will be equal after the call,
+    /// unless there are slots taken with `acquire_free_slot`, which are kept
+    /// in place.
     ///
     /// *NOTE* that this call may invalidate some indexes.
     ///
@@ -803,49 +3005,113 @@ impl<T> IndexList<T> {
     /// assert_eq!(list.len(), list.capacity());
     /// ```
     pub fn trim_swap(&mut self) {
-        let need = self.size;
+        // slots taken with acquire_free_slot can not be moved, so they are
+        // kept in place and the limit is raised above the last one
+        let detached: Vec<usize> = self.bits.slots(false, 0, self.capacity())
+            .into_iter()
+            .filter(|&at| self.is_detached(at))
+            .collect();
+        let need = (self.size + detached.len()).max(detached.last().map_or(0, |at| at + 1));
         // destination is all free node indexes below the needed limit
-        let dst: Vec<usize> = self.elems[..need]
-            .iter()
-            .enumerate()
-            .filter(|(n, e)| e.is_none() && n < &need)
-            .map(|(n, _e)| n)
+        let dst: Vec<usize> = self.bits.slots(false, 0, need)
+            .into_iter()
+            .filter(|at| detached.binary_search(at).is_err())
             .collect();
         // source is all used node indexes above the needed limit
-        let src: Vec<usize> = self.elems[need..]
-            .iter()
-            .enumerate()
-            .filter(|(_n, e)| e.is_some())
-            .map(|(n, _e)| n + need)
-            .collect();
-        debug_assert_eq!(dst.len(), src.len());
+        let src = self.bits.slots(true, need, self.capacity());
+        debug_assert!(dst.len() >= src.len());
+        if !detached.is_empty() {
+            // some free slots stay below the limit, so only unlink the others
+            (need..self.capacity()).for_each(|at| {
+                if self.is_free(at) {
+                    self.linkout_free(ListIndex::from(at));
+                }
+            });
+        }
         src.iter()
             .zip(dst.iter())
             .for_each(|(s, d)| self.replace_dest_with_source(*s, *d));
-        self.free.new_both(ListIndex::new());
+        if detached.is_empty() {
+            self.free.new_both(ListIndex::new());
+        }
         self.elems.truncate(need);
         self.nodes.truncate(need);
+        self.bits.truncate(need);
+        self.tags.truncate(need);
+    }
+    /// Create a copy of the list without any unused indexes, where the
+    /// elements are placed in list order, together with a table that maps
+    /// each index in this list to the index of the same element in the copy.
+    ///
+    /// This list is not changed, so the copy can be built while the list is
+    /// still in use, and then swapped in once the stored indexes have been
+    /// remapped.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 2, 3, 4]);
+    /// list.remove_first();
+    /// let index = list.last_index();
+    /// let (compact, remap) = list.clone_compact();
+    /// assert_eq!(compact.capacity(), 3);
+    /// assert_eq!(compact.to_string(), "[2 >< 3 >< 4]");
+    /// assert_eq!(compact.get(remap.get(index)), Some(&4));
+    /// ```
+    pub fn clone_compact(&self) -> (IndexList<T>, RemapTable)
+    where
+        T: Clone,
+    {
+        let mut list = IndexList::<T>::with_capacity(self.len());
+        list.policy = self.policy;
+        let mut remap = RemapTable::with_capacity(self.capacity());
+        let mut index = self.first_index();
+        while let Some(elem) = self.get(index) {
+            let new = list.insert_last(elem.clone());
+            if let (Some(at), Some(dst)) = (index.get(), new.get()) {
+                list.tags.set(dst, self.tags.get(at), list.capacity());
+            }
+            remap.insert(index, new);
+            index = self.next_index(index);
+        }
+        (list, remap)
     }
     /// Add the elements of the other list at the end.
     ///
     /// The other list will be empty after the call as all its elements have
     /// been moved to this list.
     ///
+    /// The storage of the other list, free indexes included, is moved in
+    /// whole after the storage of this list, so each element keeps its index
+    /// plus the capacity this list had. The returned table maps the indexes
+    /// of the other list to the new ones.
+    ///
     /// Example:
     /// ```rust
     /// # use index_list::IndexList;
     /// # let mut list = IndexList::from(&mut vec![4, 8, 15]);
     /// # let mut other = IndexList::from(&mut vec![16, 23, 42]);
     /// let sum_both = list.len() + other.len();
-    /// list.append(&mut other);
+    /// let index = other.last_index();
+    /// let remap = list.append(&mut other);
     /// assert!(other.is_empty());
     /// assert_eq!(list.len(), sum_both);
+    /// assert_eq!(list.get(remap.get(index)), Some(&42));
     /// # assert_eq!(list.to_string(), "[4 >< 8 >< 15 >< 16 >< 23 >< 42]");
     /// ```
-    pub fn append(&mut self, other: &mut IndexList<T>) {
-        while let Some(elem) = other.remove_first() {
-            self.insert_last(elem);
+    pub fn append(&mut self, other: &mut IndexList<T>) -> RemapTable {
+        let policy = other.policy;
+        #[cfg(feature = "fail-fast")]
+        let stamp = other.stamp;
+        let (remap, head, tail) = self.move_storage(mem::take(other));
+        self.join_chain(true, head, tail);
+        other.policy = policy;
+        // the other list was replaced, so its stamp must not go back
+        #[cfg(feature = "fail-fast")]
+        {
+            other.stamp = stamp.wrapping_add(1);
         }
+        remap
     }
     /// Add the elements of the other list at the beginning.
     ///
@@ -868,11 +3134,113 @@ impl<T> IndexList<T> {
             self.insert_first(elem);
         }
     }
+    /// Move the element at the index to the end of the destination list, and
+    /// return its index there, or `None` if the index is not valid.
+    ///
+    /// The tag of the element goes with it.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut todo = IndexList::from(&mut vec!["a", "b"]);
+    /// let mut done = IndexList::new();
+    /// let index = todo.transfer(todo.first_index(), &mut done);
+    /// assert_eq!(done.get(index), Some(&"a"));
+    /// assert_eq!(todo.to_string(), "[b]");
+    /// ```
+    pub fn transfer(&mut self, index: ListIndex, dest: &mut IndexList<T>) -> ListIndex {
+        let tag = index.get().map_or(0, |at| self.tags.get(at));
+        let Some(elem) = self.remove(index) else {
+            return ListIndex::new();
+        };
+        let moved = dest.insert_last(elem);
+        if let Some(at) = moved.get() {
+            dest.tags.set(at, tag, dest.capacity());
+        }
+        moved
+    }
+    /// Move all the elements, in order, to the end of the destination list
+    /// and return how many were moved.
+    ///
+    /// Unlike `dest.append(self)`, the elements are moved one by one, so the
+    /// destination reuses its free indexes before adding new ones.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![3, 4]);
+    /// let mut dest = IndexList::from(&mut vec![1, 2]);
+    /// assert_eq!(list.drain_append_to(&mut dest), 2);
+    /// assert!(list.is_empty());
+    /// assert_eq!(dest.to_string(), "[1 >< 2 >< 3 >< 4]");
+    /// ```
+    pub fn drain_append_to(&mut self, dest: &mut IndexList<T>) -> usize {
+        self.drain_append_to_if(dest, |_| true)
+    }
+    /// Move the elements where the predicate matches, in order, to the end of
+    /// the destination list and return how many were moved.
+    ///
+    /// The elements that do not match stay where they are, with the same
+    /// indexes.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut tasks = IndexList::from(&mut vec![1, 2, 3, 4, 5]);
+    /// let mut ready = IndexList::new();
+    /// assert_eq!(tasks.drain_append_to_if(&mut ready, |n| n % 2 == 1), 3);
+    /// assert_eq!(tasks.to_string(), "[2 >< 4]");
+    /// assert_eq!(ready.to_string(), "[1 >< 3 >< 5]");
+    /// ```
+    pub fn drain_append_to_if<F>(&mut self, dest: &mut IndexList<T>, mut pred: F) -> usize
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut moved = 0;
+        let mut index = self.first_index();
+        while let Some(elem) = self.get(index) {
+            let next = self.next_index(index);
+            if pred(elem) {
+                if let Some(elem) = self.remove(index) {
+                    dest.insert_last(elem);
+                    moved += 1;
+                }
+            }
+            index = next;
+        }
+        moved
+    }
+    /// Split the list in two, by the predicate, and return the elements
+    /// for which it returns `true` followed by the rest, both in list order.
+    ///
+    /// The first list is this list, so those elements keep their indexes.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let list = IndexList::from(&mut vec![1, 2, 3, 4, 5]);
+    /// let index = list.last_index();
+    /// let (odd, even) = list.partition(|&n| n % 2 == 1);
+    /// assert_eq!(odd.to_string(), "[1 >< 3 >< 5]");
+    /// assert_eq!(even.to_string(), "[2 >< 4]");
+    /// assert_eq!(odd.get(index), Some(&5));
+    /// ```
+    pub fn partition<F>(mut self, mut pred: F) -> (IndexList<T>, IndexList<T>)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut rest = IndexList::new();
+        self.drain_append_to_if(&mut rest, |elem| !pred(elem));
+        (self, rest)
+    }
     /// Split the list by moving the elements from the index to a new list.
     ///
     /// The original list will no longer contain the elements data that was
     /// moved to the other list.
     ///
+    /// The moved elements keep their indexes in the new list, which has no
+    /// more capacity than it needs for the highest of them.
+    ///
     /// Example:
     /// ```rust
     /// # use index_list::IndexList;
@@ -887,48 +3255,487 @@ impl<T> IndexList<T> {
     /// assert_eq!(list.len() + other.len(), total);
     /// # assert_eq!(list.to_string(), "[4 >< 8 >< 15]");
     /// # assert_eq!(other.to_string(), "[16 >< 23 >< 42]");
+    /// assert_eq!(other.get(index), Some(&16));
     /// ```
     pub fn split(&mut self, index: ListIndex) -> IndexList<T> {
-        let mut list = IndexList::<T>::new();
-        while self.is_index_used(index) {
-            list.insert_first(self.remove_last().unwrap());
+        let mut moved = Vec::new();
+        let mut next = if self.is_index_used(index) { index } else { ListIndex::new() };
+        while next.is_some() {
+            moved.push(next);
+            next = self.next_index(next);
+        }
+        let capacity = moved.iter().filter_map(|index| index.get()).max().map_or(0, |at| at + 1);
+        let mut keep = vec![false; capacity];
+        moved.iter().filter_map(|index| index.get()).for_each(|at| keep[at] = true);
+        let mut list = IndexList::<T>::with_capacity(capacity);
+        for (at, &kept) in keep.iter().enumerate() {
+            list.nodes.push(ListNode::new());
+            list.bits.push(false);
+            list.elems.push(None);
+            if !kept {
+                list.linkin_free(ListIndex::from(at));
+            }
+        }
+        for this in moved {
+            let tag = this.get().map_or(0, |at| self.tags.get(at));
+            list.insert_elem_at_index(this, self.remove(this));
+            list.linkin_last(this);
+            if let Some(at) = this.get() {
+                list.tags.set(at, tag, capacity);
+            }
         }
         list
     }
+    /// Split the list after the first `n` elements, in list order, and move
+    /// the rest to a new list, like `split` at the element in position `n`.
+    ///
+    /// The new list is empty if the list is not longer than `n`.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 2, 3, 4, 5]);
+    /// let other = list.split_at_ordinal(2);
+    /// assert_eq!(list.to_string(), "[1 >< 2]");
+    /// assert_eq!(other.to_string(), "[3 >< 4 >< 5]");
+    /// assert!(list.split_at_ordinal(2).is_empty());
+    /// ```
+    pub fn split_at_ordinal(&mut self, n: usize) -> IndexList<T> {
+        let index = self.get_nth(n);
+        self.split(index)
+    }
+    /// Cut out the elements from `start` up to, but not including, `end`,
+    /// and return them in a new list, in the same order.
+    ///
+    /// The range is the same as for `drain_range`, so if `end` is `None` the
+    /// elements are cut to the end of the list.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut text = IndexList::from(&mut vec!["a", "b", "c", "d"]);
+    /// let start = text.next_index(text.first_index());
+    /// let cut = text.remove_range(start, text.last_index());
+    /// assert_eq!(cut.to_string(), "[b >< c]");
+    /// assert_eq!(text.to_string(), "[a >< d]");
+    /// ```
+    pub fn remove_range(&mut self, start: ListIndex, end: ListIndex) -> IndexList<T> {
+        self.drain_range(start, end).collect()
+    }
+    /// Detach the first `n` elements, or all if there are fewer, and return
+    /// them in a new list, in the same order.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut work: IndexList<_> = (1..=5).collect();
+    /// let batch = work.take_first_n(2);
+    /// assert_eq!(batch.to_string(), "[1 >< 2]");
+    /// assert_eq!(work.to_string(), "[3 >< 4 >< 5]");
+    /// ```
+    pub fn take_first_n(&mut self, n: usize) -> IndexList<T> {
+        self.drain_iter().take(n).collect()
+    }
+    /// Detach the last `n` elements, or all if there are fewer, and return
+    /// them in a new list, in the same order.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut work: IndexList<_> = (1..=5).collect();
+    /// let batch = work.take_last_n(2);
+    /// assert_eq!(batch.to_string(), "[4 >< 5]");
+    /// assert_eq!(work.take_last_n(9).to_string(), "[1 >< 2 >< 3]");
+    /// ```
+    pub fn take_last_n(&mut self, n: usize) -> IndexList<T> {
+        let start = self.get_nth(self.size.saturating_sub(n));
+        self.remove_range(start, ListIndex::new())
+    }
+    /// Split the list into sublists at the elements where the predicate
+    /// matches, which are dropped, leaving this list empty.
+    ///
+    /// Like splitting a string, there is always one more sublist than there
+    /// were separators, and some of them may be empty.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 2, 0, 3, 0, 0, 4]);
+    /// let frames = list.split_on(|&n| n == 0);
+    /// let frames: Vec<_> = frames.iter().map(|f| f.to_string()).collect();
+    /// assert_eq!(frames, vec!["[1 >< 2]", "[3]", "[]", "[4]"]);
+    /// assert!(list.is_empty());
+    /// ```
+    pub fn split_on<F>(&mut self, pred: F) -> Vec<IndexList<T>>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.splitn(usize::MAX, pred)
+    }
+    /// Split the list into at most `n` sublists at the elements where the
+    /// predicate matches, which are dropped, leaving this list empty. The
+    /// last sublist holds the rest of the list, separators included.
+    ///
+    /// If `n` is zero, no sublists are returned and the list is not changed.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 0, 2, 0, 3]);
+    /// let frames = list.splitn(2, |&n| n == 0);
+    /// let frames: Vec<_> = frames.iter().map(|f| f.to_string()).collect();
+    /// assert_eq!(frames, vec!["[1]", "[2 >< 0 >< 3]"]);
+    /// ```
+    pub fn splitn<F>(&mut self, n: usize, mut pred: F) -> Vec<IndexList<T>>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        if n == 0 {
+            return Vec::new();
+        }
+        let mut pieces = vec![IndexList::new()];
+        while let Some(elem) = self.remove_first() {
+            if pieces.len() < n && pred(&elem) {
+                pieces.push(IndexList::new());
+            } else if let Some(piece) = pieces.last_mut() {
+                piece.insert_last(elem);
+            }
+        }
+        pieces
+    }
+    /// Split the list into sublists that each end with an element where the
+    /// predicate matches, keeping the separators, leaving this list empty.
+    /// The last sublist does not end with a separator if the list does not.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 2, 0, 3, 0, 4]);
+    /// let frames = list.split_on_inclusive(|&n| n == 0);
+    /// let frames: Vec<_> = frames.iter().map(|f| f.to_string()).collect();
+    /// assert_eq!(frames, vec!["[1 >< 2 >< 0]", "[3 >< 0]", "[4]"]);
+    /// ```
+    pub fn split_on_inclusive<F>(&mut self, mut pred: F) -> Vec<IndexList<T>>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut pieces = Vec::new();
+        let mut piece = IndexList::new();
+        while let Some(elem) = self.remove_first() {
+            let end = pred(&elem);
+            piece.insert_last(elem);
+            if end {
+                pieces.push(mem::take(&mut piece));
+            }
+        }
+        if !piece.is_empty() {
+            pieces.push(piece);
+        }
+        pieces
+    }
+    /// Split the list into `n` sublists of about the same length, in list
+    /// order, leaving this list empty. The first sublists get one element
+    /// more than the last ones when the length does not divide evenly.
+    ///
+    /// The elements get new indexes in the sublists, which can be joined
+    /// again with `append`. If `n` is zero, no sublists are returned and the
+    /// list is not changed.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list: IndexList<_> = (1..=7).collect();
+    /// let chunks = list.split_into_chunks(3);
+    /// let lens: Vec<_> = chunks.iter().map(|c| c.len()).collect();
+    /// assert_eq!(lens, vec![3, 2, 2]);
+    /// assert_eq!(chunks[1].to_string(), "[4 >< 5]");
+    /// assert!(list.is_empty());
+    /// ```
+    pub fn split_into_chunks(&mut self, n: usize) -> Vec<IndexList<T>> {
+        if n == 0 {
+            return Vec::new();
+        }
+        let (len, extra) = (self.size / n, self.size % n);
+        (0..n)
+            .map(|chunk| {
+                let take = len + usize::from(chunk < extra);
+                self.drain_iter().take(take).collect()
+            })
+            .collect()
+    }
 
+    // the first index, in list order, where the predicate matches the data
+    fn find_index<F>(&self, mut pred: F) -> ListIndex
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut index = self.first_index();
+        while let Some(elem) = self.get(index) {
+            if pred(elem) {
+                break;
+            }
+            index = self.next_index(index);
+        }
+        index
+    }
+    // remove all elements where the predicate matches and count them
+    fn remove_matching<F>(&mut self, mut pred: F) -> usize
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut removed = 0;
+        let mut index = self.first_index();
+        while let Some(elem) = self.get(index) {
+            let next = self.next_index(index);
+            if pred(elem) {
+                self.remove(index);
+                removed += 1;
+            }
+            index = next;
+        }
+        removed
+    }
+    // sort the used list by relinking, with a natural merge sort over the
+    // next links, and then restore the prev links and the list ends; when
+    // dedup is set, elements equal to the last merged one are removed
+    fn merge_sort<F>(&mut self, mut cmp: F, dedup: bool) -> usize
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut removed = 0;
+        let mut head = self.used.head;
+        loop {
+            let mut p = head;
+            let mut tail = ListIndex::new();
+            let mut merges = 0;
+            head = ListIndex::new();
+            while p.is_some() {
+                merges += 1;
+                let p_end = self.run_end(p, &mut cmp);
+                let mut q = self.next_index(p_end);
+                let (q_end, after) = if q.is_some() {
+                    let q_end = self.run_end(q, &mut cmp);
+                    (q_end, self.next_index(q_end))
+                } else {
+                    (q, q)
+                };
+                let mut p_left = true;
+                let mut q_left = q.is_some();
+                while p_left || q_left {
+                    let take_p = p_left
+                        && (!q_left || self.ordering(p, q, &mut cmp) != Ordering::Greater);
+                    let this = if take_p { p } else { q };
+                    if take_p {
+                        p_left = this != p_end;
+                        p = self.next_index(p);
+                    } else {
+                        q_left = this != q_end;
+                        q = self.next_index(q);
+                    }
+                    let duplicate = dedup
+                        && tail.is_some()
+                        && self.ordering(tail, this, &mut cmp) == Ordering::Equal;
+                    if duplicate {
+                        self.remove_elem_at_index(this);
+                        self.set_next(this, ListIndex::new());
+                        self.linkin_free(this);
+                        removed += 1;
+                    } else {
+                        if tail.is_none() {
+                            head = this;
+                        } else {
+                            self.set_next(tail, this);
+                        }
+                        tail = this;
+                    }
+                }
+                p = after;
+            }
+            self.set_next(tail, ListIndex::new());
+            if merges <= 1 {
+                break;
+            }
+        }
+        let mut prev = ListIndex::new();
+        let mut index = head;
+        while index.is_some() {
+            self.set_prev(index, prev);
+            prev = index;
+            index = self.next_index(index);
+        }
+        self.used.new_head(head);
+        self.used.new_tail(prev);
+        removed
+    }
+    // the last index of the sorted run that starts at the index
+    fn run_end<F>(&self, mut index: ListIndex, cmp: &mut F) -> ListIndex
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        loop {
+            let next = self.next_index(index);
+            if next.is_none() || self.ordering(index, next, cmp) == Ordering::Greater {
+                return index;
+            }
+            index = next;
+        }
+    }
+    // compare the element data at two valid indexes
+    fn ordering<F>(&self, this: ListIndex, that: ListIndex, cmp: &mut F) -> Ordering
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        match (self.get(this), self.get(that)) {
+            (Some(a), Some(b)) => cmp(a, b),
+            _ => Ordering::Equal,
+        }
+    }
+    // link the chain from head to tail, whose nodes are already linked to
+    // each other, after the tail of the used or the free chain
+    fn join_chain(&mut self, used: bool, head: ListIndex, tail: ListIndex) {
+        if head.is_none() {
+            return;
+        }
+        self.changed();
+        let last = if used { self.used.tail } else { self.free.tail };
+        self.set_next(last, head);
+        self.set_prev(head, last);
+        let ends = if used { &mut self.used } else { &mut self.free };
+        if ends.is_empty() {
+            ends.new_head(head);
+        }
+        ends.new_tail(tail);
+    }
+    // link the used chain from head to tail, whose nodes are already linked
+    // to each other, right after the index, or first if it is None
+    fn join_chain_after(&mut self, after: ListIndex, head: ListIndex, tail: ListIndex) {
+        if head.is_none() {
+            return;
+        }
+        self.changed();
+        let next = if after.is_none() { self.used.head } else { self.next_index(after) };
+        self.set_prev(head, after);
+        self.set_next(tail, next);
+        if after.is_none() {
+            self.used.new_head(head);
+        } else {
+            self.set_next(after, head);
+        }
+        if next.is_none() {
+            self.used.new_tail(tail);
+        } else {
+            self.set_prev(next, tail);
+        }
+    }
+    // move the storage of the other list in whole after the storage of this
+    // list and join its free chain, and return the table of the new indexes
+    // with the ends of its used chain, which is left for the caller to link
+    fn move_storage(&mut self, other: IndexList<T>) -> (RemapTable, ListIndex, ListIndex) {
+        let offset = self.capacity();
+        let mut remap = RemapTable::with_capacity(other.capacity());
+        let shift = |index: ListIndex| ListIndex::from(index.get().map(|at| at + offset));
+        for (at, node) in other.nodes.iter().enumerate() {
+            self.nodes.push(ListNode { next: shift(node.next), prev: shift(node.prev) });
+            self.bits.push(other.bits.get(at));
+            if other.bits.get(at) {
+                remap.insert(ListIndex::from(at), ListIndex::from(at + offset));
+            }
+        }
+        self.tags.append(offset, other.tags);
+        self.elems.extend(other.elems);
+        self.size += other.size;
+        self.join_chain(false, shift(other.free.head), shift(other.free.tail));
+        (remap, shift(other.used.head), shift(other.used.tail))
+    }
+    /// Move all the elements of the other list, in order, to right after the
+    /// index, or first if it is `None`, like `append` does at the end, and
+    /// return the table of their new indexes.
+    pub(crate) fn splice_list_after(&mut self, after: ListIndex, other: IndexList<T>) -> RemapTable {
+        let (remap, head, tail) = self.move_storage(other);
+        self.join_chain_after(after, head, tail);
+        remap
+    }
+    // write the elements, in order, with the separator between them
+    fn write_joined<W>(&self, out: &mut W, sep: &str) -> fmt::Result
+    where
+        W: fmt::Write,
+        T: fmt::Display,
+    {
+        let mut elems = self.iter();
+        if let Some(first) = elems.next() {
+            write!(out, "{}", first)?;
+        }
+        elems.try_for_each(|elem| write!(out, "{}{}", sep, elem))
+    }
+    // all the indexes, in list order
+    fn indexes(&self) -> Vec<ListIndex> {
+        let mut indexes = Vec::with_capacity(self.len());
+        let mut index = self.first_index();
+        while index.is_some() {
+            indexes.push(index);
+            index = self.next_index(index);
+        }
+        indexes
+    }
+    // true if a new node can be added without growing the vectors or the
+    // bitmap
+    #[inline]
+    fn has_spare_capacity(&self) -> bool {
+        self.free.head.is_some()
+            || (self.elems.len() < self.elems.capacity()
+                && self.nodes.len() < self.nodes.capacity()
+                && self.bits.has_spare())
+    }
     #[inline]
     fn is_used(&self, at: usize) -> bool {
-        self.elems[at].is_some()
+        self.bits.get(at)
     }
     fn is_free(&self, at: usize) -> bool {
-        self.elems[at].is_none()
+        !self.bits.get(at)
+    }
+    // true if the slot is empty and on neither chain, as after
+    // acquire_free_slot
+    fn is_detached(&self, at: usize) -> bool {
+        self.get_indexnode(at).is_some_and(|node| node.next.is_none() && node.prev.is_none())
+            && self.is_free(at)
+            && self.free.head != ListIndex::from(at)
     }
     #[inline]
-    fn get_mut_indexnode(&mut self, at: usize) -> &mut ListNode {
-        &mut self.nodes[at]
+    fn get_mut_indexnode(&mut self, at: usize) -> Option<&mut ListNode> {
+        self.nodes.get_mut(at)
     }
     #[inline]
-    fn get_indexnode(&self, at: usize) -> &ListNode {
-        &self.nodes[at]
+    fn get_indexnode(&self, at: usize) -> Option<&ListNode> {
+        self.nodes.get(at)
     }
     #[inline]
     fn swap_data(&mut self, here: usize, there: usize) {
         self.elems.swap(here, there);
+        self.tags.swap(here, there);
+    }
+    // called on every structural change, before the links are touched
+    #[inline]
+    fn changed(&mut self) {
+        self.finger.reset();
+        #[cfg(feature = "fail-fast")]
+        {
+            self.stamp = self.stamp.wrapping_add(1);
+        }
     }
     #[inline]
     fn set_prev(&mut self, index: ListIndex, new_prev: ListIndex) -> ListIndex {
-        if let Some(at) = index.get() {
-            self.get_mut_indexnode(at).new_prev(new_prev)
-        } else {
-            index
+        self.changed();
+        match index.get().and_then(|at| self.get_mut_indexnode(at)) {
+            Some(node) => node.new_prev(new_prev),
+            None => ListIndex::new(),
         }
     }
     #[inline]
     fn set_next(&mut self, index: ListIndex, new_next: ListIndex) -> ListIndex {
-        if let Some(at) = index.get() {
-            self.get_mut_indexnode(at).new_next(new_next)
-        } else {
-            index
+        self.changed();
+        match index.get().and_then(|at| self.get_mut_indexnode(at)) {
+            Some(node) => node.new_next(new_next),
+            None => ListIndex::new(),
         }
     }
     #[inline]
@@ -947,18 +3754,33 @@ impl<T> IndexList<T> {
     }
     #[inline]
     fn insert_elem_at_index(&mut self, this: ListIndex, elem: Option<T>) {
-        if let Some(at) = this.get() {
-            self.elems[at] = elem;
+        let Some(at) = this.get() else {
+            return;
+        };
+        if let Some(slot) = self.elems.get_mut(at) {
+            self.bits.assign(at, elem.is_some());
+            *slot = elem;
             self.size += 1;
         }
     }
     #[inline]
     fn remove_elem_at_index(&mut self, this: ListIndex) -> Option<T> {
         let at = this.get()?;
-        let removed = self.elems[at].take()?;
+        let removed = self.elems.get_mut(at)?.take()?;
+        self.bits.assign(at, false);
+        self.tags.reset(at);
         self.size -= 1;
         Some(removed)
     }
+    // the index that the next call to new_node will return
+    #[inline]
+    fn next_free_index(&self) -> ListIndex {
+        if self.free.head.is_some() {
+            self.free.head
+        } else {
+            ListIndex::from(self.nodes.len())
+        }
+    }
     fn new_node(&mut self, elem: Option<T>) -> ListIndex {
         let reuse = self.free.head;
         if reuse.is_some() {
@@ -968,6 +3790,10 @@ impl<T> IndexList<T> {
         }
         let pos = self.nodes.len();
         self.nodes.push(ListNode::new());
+        // keep room for the bits of every node, so that the bitmap does not
+        // have to grow on its own when the vectors still have spare capacity
+        self.bits.reserve(self.nodes.capacity());
+        self.bits.push(elem.is_some());
         self.elems.push(elem);
         self.size += 1;
         ListIndex::from(pos)
@@ -1059,6 +3885,12 @@ impl<T> IndexList<T> {
             debug_assert_eq!(old_head, this);
         }
     }
+    fn relink_last(&mut self, this: ListIndex) {
+        if this != self.used.tail {
+            self.linkout_used(this);
+            self.linkin_last(this);
+        }
+    }
     fn linkout_free(&mut self, this: ListIndex) {
         let (prev, next) = self.linkout_node(this);
         if next.is_none() {
@@ -1073,12 +3905,20 @@ impl<T> IndexList<T> {
     fn replace_dest_with_source(&mut self, src: usize, dst: usize) {
         debug_assert!(self.is_free(dst));
         debug_assert!(self.is_used(src));
-        self.linkout_free(ListIndex::from(dst));
-        let src_node = self.get_indexnode(src);
-        let next = src_node.next;
-        let prev = src_node.prev;
+        if !self.is_detached(dst) {
+            self.linkout_free(ListIndex::from(dst));
+        }
+        let Some(&ListNode { next, prev }) = self.get_indexnode(src) else {
+            return;
+        };
         self.linkout_used(ListIndex::from(src));
-        self.elems[dst] = self.elems[src].take();
+        let elem = self.elems.get_mut(src).and_then(Option::take);
+        if let Some(slot) = self.elems.get_mut(dst) {
+            *slot = elem;
+        }
+        self.bits.assign(dst, true);
+        self.bits.assign(src, false);
+        self.tags.copy(src, dst);
         let this = ListIndex::from(dst);
         if next.is_some() {
             self.linkin_this_before_that(this, next);
@@ -1095,8 +3935,9 @@ where
     T: fmt::Display,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let elems: Vec<String> = self.iter().map(|x| format!("{}", x)).collect();
-        write!(f, "[{}]", elems.join(" >< "))
+        write!(f, "[")?;
+        self.write_joined(f, " >< ")?;
+        write!(f, "]")
     }
 }
 
@@ -1129,14 +3970,15 @@ impl<T> Extend<T> for IndexList<T> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::mem::size_of;
+    use core::mem::size_of;
 
     #[test]
     fn test_struct_sizes() {
         assert_eq!(size_of::<ListIndex>(), 4);
         assert_eq!(size_of::<ListNode>(), 8);
         assert_eq!(size_of::<ListEnds>(), 8);
-        assert_eq!(size_of::<IndexList<u32>>(), 72);
+        #[cfg(not(any(feature = "fail-fast", feature = "tags")))]
+        assert_eq!(size_of::<IndexList<u32>>(), 120);
     }
     #[test]
     fn test_index_alias() {
@@ -1145,4 +3987,17 @@ mod tests {
         assert_eq!(ndx.get(), Some(0));
         assert_eq!(list.get(ndx), Some(&1));
     }
+    #[test]
+    fn test_within_capacity_keeps_bitmap() {
+        for (capacity, grown) in [(65, 66usize), (129, 130), (200, 201)] {
+            let mut list = IndexList::with_capacity(capacity);
+            (0..grown).for_each(|n| { list.insert_last(n); });
+            let before = (list.elems.capacity(), list.nodes.capacity(), list.bits.capacity());
+            while list.try_insert_last_within_capacity(0).is_ok() {}
+            let after = (list.elems.capacity(), list.nodes.capacity(), list.bits.capacity());
+            assert_eq!(before, after);
+            assert_eq!(list.len(), list.nodes.capacity().min(list.elems.capacity()));
+            assert!(list.len() > grown.next_multiple_of(64));
+        }
+    }
 }
@@ -0,0 +1,97 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+//! The definition of the ListAllocation type
+use alloc::vec::Vec;
+use crate::{listbitmap::ListBitmap, listends::ListEnds, listfinger::ListFinger,
+    listnode::ListNode, listpolicy::IndexPolicy, listtags::ListTags, IndexList};
+
+/// The empty buffers of a list, kept so that they can be reused by a new
+/// list instead of being freed and allocated again.
+///
+/// Example:
+/// ```rust
+/// # use index_list::IndexList;
+/// let list = IndexList::from(&mut vec![1u64, 2, 3]);
+/// let alloc = list.take_allocation();
+/// assert!(alloc.capacity() >= 3);
+/// let mut next = IndexList::with_allocation(alloc.cast::<i64>());
+/// assert!(next.is_empty());
+/// (0..3).for_each(|n| { next.insert_last(n); });
+/// assert_eq!(next.to_string(), "[0 >< 1 >< 2]");
+/// ```
+#[derive(Debug)]
+pub struct ListAllocation<T> {
+    elems: Vec<Option<T>>,
+    nodes: Vec<ListNode>,
+    bits: ListBitmap,
+    tags: ListTags,
+}
+
+impl<T> ListAllocation<T> {
+    /// Returns the number of elements a list can hold without allocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.elems.capacity().min(self.nodes.capacity())
+    }
+    /// Convert the allocation for use with another element type.
+    ///
+    /// The link buffers are always kept. The element buffer may be kept
+    /// when `Option<U>` has the same size and alignment as `Option<T>`, but
+    /// that is up to the standard library, and it is otherwise freed.
+    pub fn cast<U>(self) -> ListAllocation<U> {
+        ListAllocation {
+            elems: self.elems.into_iter().map(|_| None).collect(),
+            nodes: self.nodes,
+            bits: self.bits,
+            tags: self.tags,
+        }
+    }
+}
+
+impl<T> IndexList<T> {
+    /// Drop all the elements and return the empty buffers of the list, which
+    /// can be given to `with_allocation` to build a new list without
+    /// allocating.
+    pub fn take_allocation(mut self) -> ListAllocation<T> {
+        self.clear();
+        ListAllocation {
+            elems: self.elems,
+            nodes: self.nodes,
+            bits: self.bits,
+            tags: self.tags,
+        }
+    }
+    /// Creates a new empty list that uses the buffers of the allocation.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::<String>::with_capacity(16);
+    /// list.insert_last("request".to_string());
+    /// let list = IndexList::<String>::with_allocation(list.take_allocation());
+    /// assert!(list.is_empty());
+    /// ```
+    pub fn with_allocation(alloc: ListAllocation<T>) -> Self {
+        let ListAllocation { mut elems, mut nodes, mut bits, mut tags } = alloc;
+        elems.clear();
+        nodes.clear();
+        bits.clear();
+        tags.clear();
+        IndexList::<T> {
+            elems,
+            nodes,
+            used: ListEnds::new(),
+            free: ListEnds::new(),
+            size: 0,
+            bits,
+            finger: ListFinger::new(),
+            tags,
+            policy: IndexPolicy::default(),
+            #[cfg(feature = "fail-fast")]
+            stamp: crate::listchecked::new_stamp(),
+        }
+    }
+}
@@ -0,0 +1,157 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+//! The definition of the ListBitmap type
+//!
+use core::{default::Default, iter};
+use alloc::vec::Vec;
+
+const BITS: usize = u64::BITS as usize;
+
+/// Bitset over the vector slots, where a set bit means the slot is used. It
+/// allows scanning for used or free slots a word at a time.
+#[derive(Clone, Debug, Default)]
+pub struct ListBitmap {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl ListBitmap {
+    #[allow(dead_code)]
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        ListBitmap {
+            words: Vec::with_capacity(capacity.div_ceil(BITS)),
+            len: 0,
+        }
+    }
+    /// The number of slots that fit in the words without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.words.capacity().saturating_mul(BITS)
+    }
+    /// Make room for at least `slots` slots in total.
+    #[inline]
+    pub fn reserve(&mut self, slots: usize) {
+        self.words.reserve(slots.div_ceil(BITS).saturating_sub(self.words.len()));
+    }
+    /// True if one more slot can be pushed without reallocating.
+    #[inline]
+    pub fn has_spare(&self) -> bool {
+        self.len < self.capacity()
+    }
+    #[inline]
+    pub fn clear(&mut self) {
+        self.words.clear();
+        self.len = 0;
+    }
+    #[inline]
+    pub fn push(&mut self, used: bool) {
+        if self.len.is_multiple_of(BITS) {
+            self.words.push(0);
+        }
+        self.len += 1;
+        self.assign(self.len - 1, used);
+    }
+    #[inline]
+    pub fn get(&self, at: usize) -> bool {
+        at < self.len && self.words[at / BITS] & (1 << (at % BITS)) != 0
+    }
+    #[inline]
+    pub fn assign(&mut self, at: usize, used: bool) {
+        if at < self.len {
+            let mask = 1 << (at % BITS);
+            if used {
+                self.words[at / BITS] |= mask;
+            } else {
+                self.words[at / BITS] &= !mask;
+            }
+        }
+    }
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len {
+            self.len = len;
+            self.words.truncate(len.div_ceil(BITS));
+            if !len.is_multiple_of(BITS) {
+                if let Some(word) = self.words.last_mut() {
+                    *word &= (1 << (len % BITS)) - 1;
+                }
+            }
+        }
+    }
+    /// The number of slots, counting from the start, up to and including the
+    /// last used one.
+    pub fn used_len(&self) -> usize {
+        self.words
+            .iter()
+            .rposition(|&word| word != 0)
+            .map_or(0, |n| n * BITS + BITS - self.words[n].leading_zeros() as usize)
+    }
+    /// The used slots, in order, skipping over the words without any.
+    pub fn iter_used(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(n, &word)| {
+            let mut word = word;
+            iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let at = n * BITS + word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(at)
+            })
+        })
+    }
+    /// All the slots in the range whose used bit equals `used`.
+    pub fn slots(&self, used: bool, start: usize, end: usize) -> Vec<usize> {
+        let end = end.min(self.len);
+        let mut found = Vec::new();
+        let mut at = start;
+        while at < end {
+            let word = if used {
+                self.words[at / BITS]
+            } else {
+                !self.words[at / BITS]
+            };
+            let word = word >> (at % BITS);
+            if word == 0 {
+                at += BITS - at % BITS;
+                continue;
+            }
+            at += word.trailing_zeros() as usize;
+            if at < end {
+                found.push(at);
+            }
+            at += 1;
+        }
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bitmap_scans() {
+        let mut bits = ListBitmap::new();
+        (0..130).for_each(|i| bits.push(i % 3 == 0));
+        assert!(bits.get(129));
+        assert!(!bits.get(128));
+        assert_eq!(bits.used_len(), 130);
+        assert_eq!(bits.slots(true, 60, 70), vec![60, 63, 66, 69]);
+        assert_eq!(bits.slots(false, 126, 200), vec![127, 128]);
+        assert_eq!(bits.iter_used().skip(20).take(3).collect::<Vec<_>>(), vec![60, 63, 66]);
+        assert_eq!(bits.iter_used().count(), 44);
+        bits.truncate(65);
+        assert_eq!(bits.used_len(), 64);
+        bits.assign(63, false);
+        assert_eq!(bits.used_len(), 61);
+        assert_eq!(bits.slots(true, 0, 65).len(), 21);
+    }
+}
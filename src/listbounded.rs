@@ -0,0 +1,161 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+//! The definition of the BoundedList type
+use core::ops::Deref;
+use crate::{listindex::ListIndex, IndexList};
+
+/// The end of the list that elements are evicted from.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Evict {
+    First,
+    Last,
+}
+
+/// An index list with a maximum length.
+///
+/// When an insert makes the list longer than its maximum length, elements are
+/// evicted from the chosen end until it fits again, and each one is handed to
+/// the eviction callback together with the index it had.
+///
+/// The list can be read through `Deref`, while all changes go through the
+/// methods of the bounded list so that the bound is always upheld.
+///
+/// Example:
+/// ```rust
+/// # use index_list::{BoundedList, Evict};
+/// let mut evicted = Vec::new();
+/// let mut lru = BoundedList::new(2, Evict::First, |_index, elem| evicted.push(elem));
+/// let a = lru.insert_last("a");
+/// lru.insert_last("b");
+/// lru.touch(a);
+/// lru.insert_last("c");
+/// assert_eq!(lru.to_string(), "[a >< c]");
+/// drop(lru);
+/// assert_eq!(evicted, vec!["b"]);
+/// ```
+pub struct BoundedList<T, F>
+where
+    F: FnMut(ListIndex, T),
+{
+    list: IndexList<T>,
+    max_len: usize,
+    evict: Evict,
+    on_evict: F,
+}
+
+impl<T, F> BoundedList<T, F>
+where
+    F: FnMut(ListIndex, T),
+{
+    /// Creates a new empty bounded list, which evicts from the `evict` end
+    /// when it grows beyond `max_len` elements.
+    pub fn new(max_len: usize, evict: Evict, on_evict: F) -> Self {
+        BoundedList {
+            list: IndexList::new(),
+            max_len,
+            evict,
+            on_evict,
+        }
+    }
+    /// Returns the maximum length of the list.
+    #[inline]
+    pub fn max_len(&self) -> usize {
+        self.max_len
+    }
+    /// Change the maximum length of the list, which evicts elements right
+    /// away if the list is now too long.
+    pub fn set_max_len(&mut self, max_len: usize) {
+        self.max_len = max_len;
+        self.evict_excess();
+    }
+    /// Get a mutable reference to the element data at the index, or `None`.
+    #[inline]
+    pub fn get_mut(&mut self, index: ListIndex) -> Option<&mut T> {
+        self.list.get_mut(index)
+    }
+    /// Insert a new element at the beginning, evicting if needed.
+    ///
+    /// The returned index is invalid if the new element was itself evicted.
+    pub fn insert_first(&mut self, elem: T) -> ListIndex {
+        let index = self.list.insert_first(elem);
+        self.evict_excess();
+        index
+    }
+    /// Insert a new element at the end, evicting if needed.
+    ///
+    /// The returned index is invalid if the new element was itself evicted.
+    pub fn insert_last(&mut self, elem: T) -> ListIndex {
+        let index = self.list.insert_last(elem);
+        self.evict_excess();
+        index
+    }
+    /// Insert a new element before the index, evicting if needed.
+    ///
+    /// The returned index is invalid if the new element was itself evicted.
+    pub fn insert_before(&mut self, index: ListIndex, elem: T) -> ListIndex {
+        let index = self.list.insert_before(index, elem);
+        self.evict_excess();
+        index
+    }
+    /// Insert a new element after the index, evicting if needed.
+    ///
+    /// The returned index is invalid if the new element was itself evicted.
+    pub fn insert_after(&mut self, index: ListIndex, elem: T) -> ListIndex {
+        let index = self.list.insert_after(index, elem);
+        self.evict_excess();
+        index
+    }
+    /// Remove the element at the index and return its data, without calling
+    /// the eviction callback.
+    #[inline]
+    pub fn remove(&mut self, index: ListIndex) -> Option<T> {
+        self.list.remove(index)
+    }
+    /// Make the element at the index come last in the list, see
+    /// `IndexList::touch`.
+    #[inline]
+    pub fn touch(&mut self, index: ListIndex) -> bool {
+        self.list.touch(index)
+    }
+    /// Update the element data at the index and make it come last in the
+    /// list, see `IndexList::update_and_touch`.
+    #[inline]
+    pub fn update_and_touch<R, U>(&mut self, index: ListIndex, f: U) -> Option<R>
+    where
+        U: FnOnce(&mut T) -> R,
+    {
+        self.list.update_and_touch(index, f)
+    }
+    /// Returns the inner list, without evicting anything.
+    #[inline]
+    pub fn into_inner(self) -> IndexList<T> {
+        self.list
+    }
+
+    fn evict_excess(&mut self) {
+        while self.list.len() > self.max_len {
+            let index = match self.evict {
+                Evict::First => self.list.first_index(),
+                Evict::Last => self.list.last_index(),
+            };
+            match self.list.remove(index) {
+                Some(elem) => (self.on_evict)(index, elem),
+                None => break,
+            }
+        }
+    }
+}
+
+impl<T, F> Deref for BoundedList<T, F>
+where
+    F: FnMut(ListIndex, T),
+{
+    type Target = IndexList<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.list
+    }
+}
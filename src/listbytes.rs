@@ -0,0 +1,137 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+//! Binary snapshots of lists of plain data, behind the `bytemuck` feature
+//!
+//! The snapshot is a header of five words, the capacity followed by the head
+//! and tail of the used and free lists, then four words per slot with the
+//! next, previous, tag and used flag, and finally the element data of every
+//! slot, where free slots are zeroed. Words are `u32`, an index is stored as
+//! its slot plus one with zero meaning none, and everything is in the native
+//! byte order.
+//!
+use core::mem;
+use alloc::vec::Vec;
+use bytemuck::Pod;
+use crate::{listerror::ListError, listindex::ListIndex, listraw::{RawEnds, RawNode},
+    listtags::ListTags, IndexList};
+
+const WORD: usize = mem::size_of::<u32>();
+const HEADER_WORDS: usize = 5;
+const SLOT_WORDS: usize = 4;
+
+fn to_raw(index: ListIndex) -> u32 {
+    index.get().map_or(0, |at| at as u32 + 1)
+}
+
+fn from_raw(raw: u32) -> ListIndex {
+    ListIndex::from(raw.checked_sub(1).map(|at| at as usize))
+}
+
+fn read_word(bytes: &[u8], at: usize) -> u32 {
+    bytemuck::pod_read_unaligned(&bytes[at * WORD..(at + 1) * WORD])
+}
+
+impl<T: Pod> IndexList<T> {
+    /// Dump the list into a byte vector, keeping every index, which can be
+    /// restored with `from_bytes`.
+    ///
+    /// The snapshot uses the native byte order and the size of `T`, so it is
+    /// only meant to be read back on the same kind of machine.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1u64, 2, 3]);
+    /// let index = list.last_index();
+    /// list.remove_first();
+    /// let bytes = list.to_bytes();
+    /// let restored = IndexList::<u64>::from_bytes(&bytes).unwrap();
+    /// assert_eq!(restored.to_string(), "[2 >< 3]");
+    /// assert_eq!(restored.get(index), Some(&3));
+    /// assert_eq!(restored.capacity(), 3);
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let capacity = self.capacity();
+        let mut words = Vec::with_capacity(HEADER_WORDS + capacity * SLOT_WORDS);
+        words.push(capacity as u32);
+        for index in [self.used.head, self.used.tail, self.free.head, self.free.tail] {
+            words.push(to_raw(index));
+        }
+        for at in 0..capacity {
+            let node = self.get_indexnode(at).cloned().unwrap_or_default();
+            words.push(to_raw(node.next));
+            words.push(to_raw(node.prev));
+            words.push(self.tags.get(at));
+            words.push(u32::from(self.is_used(at)));
+        }
+        let mut bytes = bytemuck::cast_slice::<u32, u8>(&words).to_vec();
+        bytes.reserve(capacity * mem::size_of::<T>());
+        for elem in &self.elems {
+            match elem {
+                Some(data) => bytes.extend_from_slice(bytemuck::bytes_of(data)),
+                None => bytes.extend_from_slice(bytemuck::bytes_of(&T::zeroed())),
+            }
+        }
+        bytes
+    }
+    /// Restore a list from a byte snapshot made with `to_bytes`, with every
+    /// index pointing to the same element as in the list that was dumped.
+    ///
+    /// The restored list is checked with `validate`, and an error is returned
+    /// if the snapshot is truncated, its capacity is too large for its size
+    /// to be computed, or its links are not a consistent list.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::{IndexList, ListError};
+    /// let bytes = IndexList::from(&mut vec![1u32, 2]).to_bytes();
+    /// assert!(IndexList::<u32>::from_bytes(&bytes).is_ok());
+    /// let error = IndexList::<u32>::from_bytes(&bytes[1..]).unwrap_err();
+    /// assert_eq!(error, ListError::Truncated);
+    /// let mut huge = bytes.clone();
+    /// huge[..4].copy_from_slice(&u32::MAX.to_ne_bytes());
+    /// assert!(IndexList::<u32>::from_bytes(&huge).is_err());
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<IndexList<T>, ListError> {
+        if bytes.len() < HEADER_WORDS * WORD {
+            return Err(ListError::Truncated);
+        }
+        let capacity = read_word(bytes, 0) as usize;
+        // the capacity comes from the input, so the sizes may not fit
+        let elems_at = capacity
+            .checked_mul(SLOT_WORDS)
+            .and_then(|words| words.checked_add(HEADER_WORDS))
+            .and_then(|words| words.checked_mul(WORD))
+            .ok_or(ListError::TooLarge(capacity))?;
+        let len = capacity
+            .checked_mul(mem::size_of::<T>())
+            .and_then(|data| data.checked_add(elems_at))
+            .ok_or(ListError::TooLarge(capacity))?;
+        if bytes.len() != len {
+            return Err(ListError::Truncated);
+        }
+        let link = |at: usize| from_raw(read_word(bytes, at));
+        let used = RawEnds { head: link(1), tail: link(2) };
+        let free = RawEnds { head: link(3), tail: link(4) };
+        let mut elems = Vec::with_capacity(capacity);
+        let mut nodes = Vec::with_capacity(capacity);
+        let mut tags = Vec::with_capacity(capacity);
+        let mut size = 0;
+        for at in 0..capacity {
+            let word = HEADER_WORDS + at * SLOT_WORDS;
+            nodes.push(RawNode { next: link(word), prev: link(word + 1) });
+            tags.push(read_word(bytes, word + 2));
+            let used = read_word(bytes, word + 3) != 0;
+            let start = elems_at + at * mem::size_of::<T>();
+            let data = bytemuck::pod_read_unaligned(&bytes[start..start + mem::size_of::<T>()]);
+            elems.push(used.then_some(data));
+            size += usize::from(used);
+        }
+        let mut list = IndexList::from_raw_parts((elems, nodes, used, free, size))?;
+        list.tags = ListTags::from_vec(tags);
+        Ok(list)
+    }
+}
@@ -0,0 +1,116 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+//! The definition of the ListCells type
+use core::cell::Cell;
+use crate::{listends::ListEnds, listindex::ListIndex, listnode::ListNode};
+
+/// A view of the list where every element is a cell, so that several
+/// elements can be changed through shared references while the list is
+/// walked, for instance in graph algorithms that update the neighbours of
+/// the current element.
+///
+/// The view is created with `IndexList::as_cells`, which borrows the list
+/// mutably, so the structure of the list can not change while it is used.
+///
+/// Example:
+/// ```rust
+/// # use index_list::IndexList;
+/// let mut list = IndexList::from(&mut vec![1, 2, 3, 4]);
+/// let cells = list.as_cells();
+/// let mut index = cells.next_index(cells.first_index());
+/// while index.is_some() {
+///     let prev = cells.prev_index(index);
+///     let sum = cells.get(prev).unwrap() + cells.get(index).unwrap();
+///     cells.set(index, sum);
+///     index = cells.next_index(index);
+/// }
+/// assert_eq!(list.to_string(), "[1 >< 3 >< 6 >< 10]");
+/// ```
+pub struct ListCells<'a, T> {
+    elems: &'a [Cell<Option<T>>],
+    nodes: &'a [ListNode],
+    used: &'a ListEnds,
+}
+
+impl<'a, T> ListCells<'a, T> {
+    pub(crate) fn new(elems: &'a mut [Option<T>], nodes: &'a [ListNode], used: &'a ListEnds)
+        -> Self
+    {
+        ListCells {
+            elems: Cell::from_mut(elems).as_slice_of_cells(),
+            nodes,
+            used,
+        }
+    }
+    /// Get the index of the first element, or `None`.
+    #[inline]
+    pub fn first_index(&self) -> ListIndex {
+        self.used.head
+    }
+    /// Get the index of the last element, or `None`.
+    #[inline]
+    pub fn last_index(&self) -> ListIndex {
+        self.used.tail
+    }
+    /// Get the index of the element after the index, or `None`. The index
+    /// after `None` is the first index.
+    #[inline]
+    pub fn next_index(&self, index: ListIndex) -> ListIndex {
+        match index.get() {
+            Some(at) => self.nodes.get(at).map(|node| node.next).unwrap_or_default(),
+            None => self.first_index(),
+        }
+    }
+    /// Get the index of the element before the index, or `None`. The index
+    /// before `None` is the last index.
+    #[inline]
+    pub fn prev_index(&self, index: ListIndex) -> ListIndex {
+        match index.get() {
+            Some(at) => self.nodes.get(at).map(|node| node.prev).unwrap_or_default(),
+            None => self.last_index(),
+        }
+    }
+    /// Get a copy of the element data at the index, or `None`.
+    #[inline]
+    pub fn get(&self, index: ListIndex) -> Option<T>
+    where
+        T: Copy,
+    {
+        self.cell(index)?.get()
+    }
+    /// Set the element data at the index, returning `false` if the index is
+    /// not valid, in which case nothing is changed.
+    pub fn set(&self, index: ListIndex, elem: T) -> bool {
+        self.replace(index, elem).is_some()
+    }
+    /// Replace the element data at the index and return the old data, or
+    /// `None` if the index is not valid, in which case nothing is changed.
+    pub fn replace(&self, index: ListIndex, elem: T) -> Option<T> {
+        let cell = self.cell(index)?;
+        let old = cell.take();
+        if old.is_some() {
+            cell.set(Some(elem));
+        }
+        old
+    }
+    /// Update the element data at the index with a closure that gets the
+    /// current data and returns the new data. Returns `false` if the index is
+    /// not valid.
+    pub fn update<F>(&self, index: ListIndex, f: F) -> bool
+    where
+        T: Copy,
+        F: FnOnce(T) -> T,
+    {
+        match self.get(index) {
+            Some(elem) => self.set(index, f(elem)),
+            None => false,
+        }
+    }
+    #[inline]
+    fn cell(&self, index: ListIndex) -> Option<&'a Cell<Option<T>>> {
+        self.elems.get(index.get()?)
+    }
+}
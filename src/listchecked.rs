@@ -0,0 +1,88 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+//! The definition of the CheckedIter type
+#[cfg(target_has_atomic = "32")]
+use core::sync::atomic::{AtomicU32, Ordering};
+use crate::{listerror::ListError, listindex::ListIndex, IndexList};
+
+/// The number of lists created so far, which makes up the high half of the
+/// first stamp of each new list.
+#[cfg(target_has_atomic = "32")]
+static LISTS: AtomicU32 = AtomicU32::new(0);
+
+/// Returns the first stamp of a new list, which does not repeat the stamps
+/// of the lists created before it, as each list counts its changes in the
+/// low half. On targets without 32-bit atomics every list starts at zero.
+#[cfg(target_has_atomic = "32")]
+pub(crate) fn new_stamp() -> u64 {
+    u64::from(LISTS.fetch_add(1, Ordering::Relaxed)) << 32
+}
+
+#[cfg(not(target_has_atomic = "32"))]
+pub(crate) fn new_stamp() -> u64 {
+    0
+}
+
+/// A forward iterator that does not borrow the list, like `ResumableIter`,
+/// but fails fast instead of re-synchronizing when the structure of the list
+/// has changed since the iterator was created.
+///
+/// The list is passed to each call, and its structure stamp is compared with
+/// the one saved when the iterator was created. Changing the element data is
+/// allowed, while inserting, removing or moving any element makes the next
+/// call return `ListError::StructureChanged`. So does replacing the list
+/// with another one, for instance a new or a cloned list, except on targets
+/// without 32-bit atomics, where every new list starts with the same stamp.
+///
+/// Only available with the `fail-fast` feature.
+///
+/// Example:
+/// ```rust
+/// # use index_list::{IndexList, ListError};
+/// let mut list = IndexList::from(&mut vec![1, 2, 3]);
+/// let mut scan = list.iter_checked();
+/// let (index, _) = scan.next(&list).unwrap().unwrap();
+/// list.update(index, |data| *data = 10);
+/// assert!(scan.next(&list).is_ok());
+/// list.remove(index);
+/// assert_eq!(scan.next(&list), Err(ListError::StructureChanged));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct CheckedIter {
+    next: ListIndex,
+    stamp: u64,
+}
+
+impl CheckedIter {
+    /// Creates a new iterator that starts at the first element of the list.
+    pub fn new<T>(list: &IndexList<T>) -> Self {
+        CheckedIter {
+            next: list.first_index(),
+            stamp: list.structure_stamp(),
+        }
+    }
+    /// Returns the index of the element that will be returned next.
+    #[inline]
+    pub fn next_index(&self) -> ListIndex {
+        self.next
+    }
+    /// Returns the next element data together with its index, `None` when
+    /// the end is reached, or an error if the structure of the list has
+    /// changed since the iterator was created.
+    pub fn next<'a, T>(&mut self, list: &'a IndexList<T>)
+        -> Result<Option<(ListIndex, &'a T)>, ListError>
+    {
+        if list.structure_stamp() != self.stamp {
+            return Err(ListError::StructureChanged);
+        }
+        let index = self.next;
+        let Some(elem) = list.get(index) else {
+            return Ok(None);
+        };
+        self.next = list.next_index(index);
+        Ok(Some((index, elem)))
+    }
+}
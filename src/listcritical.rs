@@ -0,0 +1,112 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+//! The definition of the CriticalList type, behind the `embedded` feature
+use core::cell::RefCell;
+use critical_section::Mutex;
+use crate::{listindex::ListIndex, IndexList};
+
+/// An index list that can be shared between an interrupt handler and the
+/// main context, where every access runs inside a critical section.
+///
+/// The list is created on first use, so `new` is `const` and the list can
+/// be a `static`. The interrupt handler typically pushes to the back, and
+/// the main loop takes the whole list, which keeps the critical section
+/// short, and then drains it at leisure.
+///
+/// The interrupt handler should use `try_push`, which never allocates and
+/// gives the element back once the capacity is used up, and the main loop
+/// should hand over a list with spare capacity with `swap`, such as the one
+/// it drained the last time, whatever way that list had grown. This works
+/// without the `std` feature, as long as there is a global allocator.
+///
+/// Example:
+/// ```rust
+/// # use index_list::{CriticalList, IndexList};
+/// static QUEUE: CriticalList<u8> = CriticalList::new();
+/// let mut spare = IndexList::with_capacity(2);
+/// spare = QUEUE.swap(spare);
+/// // in the interrupt handler
+/// assert!(QUEUE.try_push(1).is_ok());
+/// assert!(QUEUE.try_push(2).is_ok());
+/// assert_eq!(QUEUE.try_push(3), Err(3));
+/// // in the main loop
+/// spare.clear();
+/// let pending = QUEUE.swap(spare);
+/// assert_eq!(pending.to_string(), "[1 >< 2]");
+/// assert!(QUEUE.is_empty());
+/// // a list that grew while it was pushed to is handed back just as well
+/// let mut grown = IndexList::with_capacity(65);
+/// (0..66).for_each(|n| { grown.insert_last(n); });
+/// grown.clear();
+/// QUEUE.swap(grown);
+/// let pushed = (0..=255).take_while(|&n| QUEUE.try_push(n).is_ok()).count();
+/// assert!(pushed >= 66);
+/// assert_eq!(QUEUE.len(), pushed);
+/// ```
+#[derive(Debug)]
+pub struct CriticalList<T> {
+    list: Mutex<RefCell<Option<IndexList<T>>>>,
+}
+
+impl<T> Default for CriticalList<T> {
+    fn default() -> Self {
+        CriticalList::new()
+    }
+}
+
+impl<T> CriticalList<T> {
+    /// Creates a new empty list.
+    pub const fn new() -> Self {
+        CriticalList {
+            list: Mutex::new(RefCell::new(None)),
+        }
+    }
+    /// Run the closure on the list inside a critical section and return its
+    /// result.
+    pub fn with<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut IndexList<T>) -> R,
+    {
+        critical_section::with(|cs| {
+            let mut list = self.list.borrow_ref_mut(cs);
+            f(list.get_or_insert_with(IndexList::new))
+        })
+    }
+    /// Insert a new element at the end.
+    ///
+    /// This may allocate inside the critical section, see `try_push`.
+    pub fn push(&self, elem: T) -> ListIndex {
+        self.with(|list| list.insert_last(elem))
+    }
+    /// Insert a new element at the end, but only if that can be done without
+    /// allocating memory, or return the element back as an error.
+    pub fn try_push(&self, elem: T) -> Result<ListIndex, T> {
+        self.with(|list| list.try_insert_last_within_capacity(elem))
+    }
+    /// Remove the first element and return its data.
+    pub fn pop(&self) -> Option<T> {
+        self.with(|list| list.remove_first())
+    }
+    /// Returns `true` when the list is empty.
+    pub fn is_empty(&self) -> bool {
+        self.with(|list| list.is_empty())
+    }
+    /// Returns the number of elements.
+    pub fn len(&self) -> usize {
+        self.with(|list| list.len())
+    }
+    /// Take the whole list, leaving an empty list in its place.
+    pub fn take(&self) -> IndexList<T> {
+        critical_section::with(|cs| self.list.borrow_ref_mut(cs).take().unwrap_or_default())
+    }
+    /// Take the whole list, leaving the given list in its place, so that its
+    /// capacity can be used by `try_push`.
+    pub fn swap(&self, list: IndexList<T>) -> IndexList<T> {
+        critical_section::with(|cs| {
+            self.list.borrow_ref_mut(cs).replace(list).unwrap_or_default()
+        })
+    }
+}
@@ -0,0 +1,86 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+//! The definition of the Cursor type
+use crate::{listindex::ListIndex, IndexList};
+
+/// A cursor over the list, which remembers its position and can be moved
+/// back and forth.
+///
+/// Just as for the cursor of `LinkedList`, there is a position between the
+/// last and the first element that has no element, where the index is
+/// `None`. Moving next from the last element, or previous from the first,
+/// puts the cursor there, and moving on from there wraps around.
+///
+/// Example:
+/// ```rust
+/// # use index_list::IndexList;
+/// let list = IndexList::from(&mut vec![1, 2, 3]);
+/// let mut cursor = list.cursor_front();
+/// assert_eq!(cursor.current(), Some(&1));
+/// cursor.move_next();
+/// assert_eq!(cursor.peek_prev(), Some(&1));
+/// assert_eq!(cursor.peek_next(), Some(&3));
+/// cursor.move_next();
+/// cursor.move_next();
+/// assert_eq!(cursor.current(), None);
+/// assert_eq!(cursor.peek_next(), Some(&1));
+/// ```
+pub struct Cursor<'a, T> {
+    list: &'a IndexList<T>,
+    index: ListIndex,
+}
+
+impl<'a, T> Cursor<'a, T> {
+    pub(crate) fn new(list: &'a IndexList<T>, index: ListIndex) -> Self {
+        let index = if list.is_index_used(index) { index } else { ListIndex::new() };
+        Cursor { list, index }
+    }
+    /// Returns the index of the current element, or `None` between the last
+    /// and the first element.
+    #[inline]
+    pub fn index(&self) -> ListIndex {
+        self.index
+    }
+    /// Returns the current element data, or `None` between the last and the
+    /// first element.
+    #[inline]
+    pub fn current(&self) -> Option<&'a T> {
+        self.list.get(self.index)
+    }
+    /// Move the cursor to the next element.
+    #[inline]
+    pub fn move_next(&mut self) {
+        self.index = self.list.next_index(self.index);
+    }
+    /// Move the cursor to the previous element.
+    #[inline]
+    pub fn move_prev(&mut self) {
+        self.index = self.list.prev_index(self.index);
+    }
+    /// Returns the data of the element after the current one, without moving
+    /// the cursor.
+    #[inline]
+    pub fn peek_next(&self) -> Option<&'a T> {
+        self.list.get(self.list.next_index(self.index))
+    }
+    /// Returns the data of the element before the current one, without
+    /// moving the cursor.
+    #[inline]
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        self.list.get(self.list.prev_index(self.index))
+    }
+    /// Returns the list the cursor walks.
+    #[inline]
+    pub fn as_list(&self) -> &'a IndexList<T> {
+        self.list
+    }
+}
+
+impl<T> Clone for Cursor<'_, T> {
+    fn clone(&self) -> Self {
+        Cursor { list: self.list, index: self.index }
+    }
+}
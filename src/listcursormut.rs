@@ -0,0 +1,154 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+//! The definition of the CursorMut type
+use crate::{listcursor::Cursor, listindex::ListIndex, listremap::RemapTable, IndexList};
+
+/// A cursor over the list that can change the list while it walks it.
+///
+/// As with `Cursor`, the position between the last and the first element
+/// has no element and the index `None`. Inserting after it inserts first,
+/// and inserting before it inserts last.
+///
+/// Example:
+/// ```rust
+/// # use index_list::IndexList;
+/// let mut list = IndexList::from(&mut vec![1, 2, 3, 4]);
+/// let mut cursor = list.cursor_mut_front();
+/// while let Some(&mut elem) = cursor.current() {
+///     if elem % 2 == 0 {
+///         cursor.remove_current();
+///     } else {
+///         cursor.insert_after(elem * 10);
+///         cursor.move_next();
+///         cursor.move_next();
+///     }
+/// }
+/// assert_eq!(list.to_string(), "[1 >< 10 >< 3 >< 30]");
+/// ```
+pub struct CursorMut<'a, T> {
+    list: &'a mut IndexList<T>,
+    index: ListIndex,
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    pub(crate) fn new(list: &'a mut IndexList<T>, index: ListIndex) -> Self {
+        let index = if list.is_index_used(index) { index } else { ListIndex::new() };
+        CursorMut { list, index }
+    }
+    /// Returns the index of the current element, or `None` between the last
+    /// and the first element.
+    #[inline]
+    pub fn index(&self) -> ListIndex {
+        self.index
+    }
+    /// Returns the current element data, or `None` between the last and the
+    /// first element.
+    #[inline]
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.list.get_mut(self.index)
+    }
+    /// Move the cursor to the next element.
+    #[inline]
+    pub fn move_next(&mut self) {
+        self.index = self.list.next_index(self.index);
+    }
+    /// Move the cursor to the previous element.
+    #[inline]
+    pub fn move_prev(&mut self) {
+        self.index = self.list.prev_index(self.index);
+    }
+    /// Returns the data of the element after the current one, without moving
+    /// the cursor.
+    #[inline]
+    pub fn peek_next(&self) -> Option<&T> {
+        self.list.get(self.list.next_index(self.index))
+    }
+    /// Returns the data of the element before the current one, without
+    /// moving the cursor.
+    #[inline]
+    pub fn peek_prev(&self) -> Option<&T> {
+        self.list.get(self.list.prev_index(self.index))
+    }
+    /// Insert a new element before the current one, and return its index.
+    /// The cursor does not move.
+    pub fn insert_before(&mut self, elem: T) -> ListIndex {
+        if self.index.is_none() {
+            self.list.insert_last(elem)
+        } else {
+            self.list.insert_before(self.index, elem)
+        }
+    }
+    /// Insert a new element after the current one, and return its index.
+    /// The cursor does not move.
+    pub fn insert_after(&mut self, elem: T) -> ListIndex {
+        if self.index.is_none() {
+            self.list.insert_first(elem)
+        } else {
+            self.list.insert_after(self.index, elem)
+        }
+    }
+    /// Remove the current element and return its data, moving the cursor to
+    /// the next element. Returns `None` between the last and the first
+    /// element, where nothing is removed.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let next = self.list.next_index(self.index);
+        let elem = self.list.remove(self.index)?;
+        self.index = next;
+        Some(elem)
+    }
+    /// Move all the elements of the other list, in order, to right after
+    /// the current element, or first in the list between the last and the
+    /// first element. The cursor does not move.
+    ///
+    /// The storage of the other list is moved in whole, as with
+    /// `IndexList::append`, and its chain is linked in at the cursor in one
+    /// step. The returned table maps the indexes of the other list to the
+    /// new ones.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 4]);
+    /// let other = IndexList::from(&mut vec![2, 3]);
+    /// let index = other.last_index();
+    /// let mut cursor = list.cursor_mut_front();
+    /// let remap = cursor.splice_after(other);
+    /// assert_eq!(cursor.current(), Some(&mut 1));
+    /// assert_eq!(list.get(remap.get(index)), Some(&3));
+    /// assert_eq!(list.to_string(), "[1 >< 2 >< 3 >< 4]");
+    /// ```
+    pub fn splice_after(&mut self, other: IndexList<T>) -> RemapTable {
+        self.list.splice_list_after(self.index, other)
+    }
+    /// Move all the elements of the other list, in order, to right before
+    /// the current element, or last in the list between the last and the
+    /// first element. The cursor does not move.
+    ///
+    /// The storage is moved as with `splice_after`, and the returned table
+    /// maps the indexes of the other list to the new ones.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 4]);
+    /// let mut cursor = list.cursor_mut_back();
+    /// cursor.splice_before(IndexList::from(&mut vec![2, 3]));
+    /// assert_eq!(cursor.current(), Some(&mut 4));
+    /// cursor.move_next();
+    /// cursor.splice_before(IndexList::from(&mut vec![5]));
+    /// assert_eq!(list.to_string(), "[1 >< 2 >< 3 >< 4 >< 5]");
+    /// ```
+    pub fn splice_before(&mut self, other: IndexList<T>) -> RemapTable {
+        let after = self.list.prev_index(self.index);
+        self.list.splice_list_after(after, other)
+    }
+    /// Returns a read-only cursor at the same position, which borrows this
+    /// cursor.
+    #[inline]
+    pub fn as_cursor(&self) -> Cursor<'_, T> {
+        Cursor::new(self.list, self.index)
+    }
+}
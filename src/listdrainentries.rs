@@ -0,0 +1,46 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+//! The definition of the ListDrainEntries type
+use core::iter::{DoubleEndedIterator, ExactSizeIterator, FusedIterator};
+use crate::{listindex::ListIndex, IndexList};
+
+/// A consuming iterator that will remove elements from the list as it is
+/// iterating over them, yielding the index each element had together with
+/// its data. The iterator is fused and can also be reversed.
+///
+/// Dropping the iterator does not remove anything, so the elements that were
+/// not iterated over stay in the list and keep their indexes.
+pub struct ListDrainEntries<'a, T>(&'a mut IndexList<T>);
+
+impl<'a, T> ListDrainEntries<'a, T> {
+    pub fn new(list: &'a mut IndexList<T>) -> Self {
+        ListDrainEntries::<'a, T>(list)
+    }
+}
+
+impl<T> Iterator for ListDrainEntries<'_, T> {
+    type Item = (ListIndex, T);
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.0.first_index();
+        Some((index, self.0.remove(index)?))
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.0.len();
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T> DoubleEndedIterator for ListDrainEntries<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let index = self.0.last_index();
+        Some((index, self.0.remove(index)?))
+    }
+}
+
+impl<T> FusedIterator for ListDrainEntries<'_, T> {}
+
+impl<T> ExactSizeIterator for ListDrainEntries<'_, T> {}
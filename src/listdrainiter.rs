@@ -4,11 +4,14 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 //! The definition of the ListDrainIter type
-use std::iter::{DoubleEndedIterator, FusedIterator};
+use core::iter::{DoubleEndedIterator, ExactSizeIterator, FusedIterator};
 use crate::{listiter::ListIter, IndexList};
 
 /// A consuming interator that will remove elements from the list as it is
 /// iterating over them. The iterator is fused and can also be reversed.
+///
+/// Dropping the iterator does not remove anything, so the elements that were
+/// not iterated over stay in the list and keep their indexes.
 pub struct ListDrainIter<'a, T>(&'a mut IndexList<T>);
 
 impl<'a, T> ListDrainIter<'a, T> {
@@ -22,6 +25,11 @@ impl<T> Iterator for ListDrainIter<'_, T> {
     fn next(&mut self) -> Option<Self::Item> {
         self.0.remove_first()
     }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.0.len();
+        (remaining, Some(remaining))
+    }
 }
 
 impl<T> DoubleEndedIterator for ListDrainIter<'_, T> {
@@ -32,6 +40,8 @@ impl<T> DoubleEndedIterator for ListDrainIter<'_, T> {
 
 impl<T> FusedIterator for ListDrainIter<'_, T> {}
 
+impl<T> ExactSizeIterator for ListDrainIter<'_, T> {}
+
 impl<'a, T> IntoIterator for &'a IndexList<T> {
     type Item = &'a T;
     type IntoIter = ListIter<'a, T>;
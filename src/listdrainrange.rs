@@ -0,0 +1,48 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+//! The definition of the ListDrainRange type
+use core::iter::FusedIterator;
+use crate::{listindex::ListIndex, IndexList};
+
+/// A consuming iterator that removes the elements of a range of the list,
+/// from a start index up to, but not including, an end index. It is fused.
+///
+/// Dropping the iterator does not remove anything, so the elements of the
+/// range that were not iterated over stay in the list.
+pub struct ListDrainRange<'a, T> {
+    list: &'a mut IndexList<T>,
+    next: ListIndex,
+    end: ListIndex,
+}
+
+impl<'a, T> ListDrainRange<'a, T> {
+    pub fn new(list: &'a mut IndexList<T>, start: ListIndex, end: ListIndex) -> Self {
+        let next = if list.is_index_used(start) { start } else { ListIndex::new() };
+        ListDrainRange { list, next, end }
+    }
+}
+
+impl<T> Iterator for ListDrainRange<'_, T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next.is_none() || self.next == self.end {
+            return None;
+        }
+        let this = self.next;
+        self.next = self.list.next_index(this);
+        self.list.remove(this)
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.next.is_none() || self.next == self.end {
+            (0, Some(0))
+        } else {
+            (1, Some(self.list.len()))
+        }
+    }
+}
+
+impl<T> FusedIterator for ListDrainRange<'_, T> {}
@@ -0,0 +1,57 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+//! The definition of the ListDrainWhile type
+use core::iter::FusedIterator;
+use crate::IndexList;
+
+/// A consuming iterator that will remove elements from the front of the list
+/// for as long as the predicate holds. It stops at the first element where
+/// the predicate fails, which is left in the list, and it is fused.
+///
+/// Only the elements that are actually iterated over are removed.
+pub struct ListDrainWhile<'a, T, F> {
+    list: &'a mut IndexList<T>,
+    pred: F,
+    done: bool,
+}
+
+impl<'a, T, F> ListDrainWhile<'a, T, F>
+where
+    F: FnMut(&T) -> bool,
+{
+    pub fn new(list: &'a mut IndexList<T>, pred: F) -> Self {
+        ListDrainWhile { list, pred, done: false }
+    }
+}
+
+impl<T, F> Iterator for ListDrainWhile<'_, T, F>
+where
+    F: FnMut(&T) -> bool,
+{
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.list.get_first() {
+            Some(elem) if (self.pred)(elem) => self.list.remove_first(),
+            _ => {
+                self.done = true;
+                None
+            }
+        }
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else {
+            (0, Some(self.list.len()))
+        }
+    }
+}
+
+impl<T, F> FusedIterator for ListDrainWhile<'_, T, F> where F: FnMut(&T) -> bool {}
@@ -5,7 +5,7 @@
  */
 //! The defenition of the ListEnds type
 //!
-use std::{default::Default, fmt, mem};
+use core::{default::Default, fmt, mem};
 use crate::listindex::ListIndex;
 
 #[derive(Clone, Debug, Default)]
@@ -0,0 +1,65 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+//! The definition of the ListError type
+use core::{error::Error, fmt};
+use crate::listindex::ListIndex;
+
+/// The invariant that a list failed, when it is rebuilt from its raw parts
+/// or from a snapshot, or the failure of a checked insert or walk.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ListError {
+    /// The snapshot is shorter or longer than its header says.
+    Truncated,
+    /// The vectors of elements and nodes have different lengths.
+    LengthMismatch { elems: usize, nodes: usize },
+    /// There are more slots than an index can address.
+    TooLarge(usize),
+    /// The link points outside of the vectors.
+    OutOfRange(ListIndex),
+    /// The node does not link back to the node before it.
+    BrokenLink(ListIndex),
+    /// The used chain reaches an empty slot, or the free chain an element.
+    WrongChain(ListIndex),
+    /// The chain reaches the index a second time.
+    Cycle(ListIndex),
+    /// The chain does not end at its tail.
+    WrongTail { tail: ListIndex, last: ListIndex },
+    /// The slot is not on any chain.
+    Unlinked(ListIndex),
+    /// The size does not match the number of elements.
+    SizeMismatch { size: usize, elems: usize },
+    /// The index is not `None` and does not refer to an element.
+    InvalidIndex(ListIndex),
+    /// The structure of the list changed while it was walked.
+    StructureChanged,
+}
+
+impl fmt::Display for ListError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ListError::Truncated => write!(f, "the snapshot has the wrong length"),
+            ListError::LengthMismatch { elems, nodes } => {
+                write!(f, "{} elements but {} nodes", elems, nodes)
+            }
+            ListError::TooLarge(len) => write!(f, "{} slots can not be indexed", len),
+            ListError::OutOfRange(index) => write!(f, "link {} is out of range", index),
+            ListError::BrokenLink(index) => write!(f, "node {} does not link back", index),
+            ListError::WrongChain(index) => write!(f, "node {} is on the wrong chain", index),
+            ListError::Cycle(index) => write!(f, "node {} is part of a cycle", index),
+            ListError::WrongTail { tail, last } => {
+                write!(f, "chain ends at {} instead of {}", last, tail)
+            }
+            ListError::Unlinked(index) => write!(f, "node {} is not on any chain", index),
+            ListError::SizeMismatch { size, elems } => {
+                write!(f, "size is {} but there are {} elements", size, elems)
+            }
+            ListError::InvalidIndex(index) => write!(f, "index {} is not valid", index),
+            ListError::StructureChanged => write!(f, "the list changed while it was walked"),
+        }
+    }
+}
+
+impl Error for ListError {}
@@ -0,0 +1,56 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+//! The definition of the ListExtractIf type
+use core::iter::FusedIterator;
+use crate::{listindex::ListIndex, IndexList};
+
+/// A consuming iterator that walks the list and removes the elements for
+/// which the predicate returns `true`, and it is fused.
+///
+/// The other elements are left in the list with their indexes, and only the
+/// part of the list that is actually iterated over is visited.
+pub struct ListExtractIf<'a, T, F> {
+    list: &'a mut IndexList<T>,
+    pred: F,
+    next: ListIndex,
+}
+
+impl<'a, T, F> ListExtractIf<'a, T, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    pub fn new(list: &'a mut IndexList<T>, pred: F) -> Self {
+        let next = list.first_index();
+        ListExtractIf { list, pred, next }
+    }
+}
+
+impl<T, F> Iterator for ListExtractIf<'_, T, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next.is_some() {
+            let index = self.next;
+            self.next = self.list.next_index(index);
+            if self.list.get_mut(index).is_some_and(|elem| (self.pred)(elem)) {
+                return self.list.remove(index);
+            }
+        }
+        None
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.next.is_none() {
+            (0, Some(0))
+        } else {
+            (0, Some(self.list.len()))
+        }
+    }
+}
+
+impl<T, F> FusedIterator for ListExtractIf<'_, T, F> where F: FnMut(&mut T) -> bool {}
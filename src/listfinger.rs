@@ -0,0 +1,69 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+//! The definition of the ListFinger type
+//!
+#[cfg(target_has_atomic = "64")]
+use core::sync::atomic::{AtomicU64, Ordering};
+use core::default::Default;
+use crate::listindex::ListIndex;
+
+/// Cache of the last looked up index and its position in the list, which
+/// shortens the walk for positional lookups near the same place.
+///
+/// Both are packed in a single word, so that it can be updated through a
+/// shared reference while the list remains `Sync`. Zero means empty. On
+/// targets without 64-bit atomics nothing is cached.
+#[derive(Debug, Default)]
+pub struct ListFinger {
+    #[cfg(target_has_atomic = "64")]
+    cache: AtomicU64,
+}
+
+#[cfg(target_has_atomic = "64")]
+impl ListFinger {
+    #[allow(dead_code)]
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+    #[inline]
+    pub fn get(&self) -> Option<(ListIndex, usize)> {
+        let word = self.cache.load(Ordering::Relaxed);
+        let slot = (word >> 32) as usize;
+        if slot == 0 {
+            return None;
+        }
+        Some((ListIndex::from(slot - 1), (word & u64::from(u32::MAX)) as usize))
+    }
+    #[inline]
+    pub fn set(&self, index: ListIndex, pos: usize) {
+        if let (Some(at), Ok(pos)) = (index.get(), u32::try_from(pos)) {
+            let word = ((at as u64 + 1) << 32) | u64::from(pos);
+            self.cache.store(word, Ordering::Relaxed);
+        }
+    }
+    #[inline]
+    pub fn reset(&mut self) {
+        *self.cache.get_mut() = 0;
+    }
+}
+
+#[cfg(not(target_has_atomic = "64"))]
+impl ListFinger {
+    #[allow(dead_code)]
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+    #[inline]
+    pub fn get(&self) -> Option<(ListIndex, usize)> {
+        None
+    }
+    #[inline]
+    pub fn set(&self, _index: ListIndex, _pos: usize) {}
+    #[inline]
+    pub fn reset(&mut self) {}
+}
@@ -0,0 +1,343 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+//! The definition of the ForwardList type
+use core::{fmt, iter::{FromIterator, FusedIterator}};
+use alloc::vec::Vec;
+use crate::{listends::ListEnds, listindex::ListIndex};
+
+/// A singly-linked index list, where every element only links to the next
+/// one, which halves the memory used for the links compared to `IndexList`.
+///
+/// The list can only be walked forward, and elements can only be removed at
+/// the front or right after a known index, which fits buffers that are
+/// appended to and drained from the front. Indexes are stable and freed
+/// indexes are reused just as in `IndexList`.
+///
+/// Example:
+/// ```rust
+/// # use index_list::ForwardList;
+/// let mut buffer = ForwardList::new();
+/// let first = buffer.insert_last(1);
+/// buffer.insert_last(3);
+/// buffer.insert_after(first, 2);
+/// assert_eq!(buffer.to_string(), "[1 > 2 > 3]");
+/// assert_eq!(buffer.drain().collect::<Vec<_>>(), vec![1, 2, 3]);
+/// assert!(buffer.is_empty());
+/// ```
+#[derive(Clone, Debug)]
+pub struct ForwardList<T> {
+    elems: Vec<Option<T>>,
+    next: Vec<ListIndex>,
+    used: ListEnds,
+    free: ListEnds,
+    size: usize,
+}
+
+impl<T> Default for ForwardList<T> {
+    fn default() -> Self {
+        ForwardList::<T> {
+            elems: Vec::new(),
+            next: Vec::new(),
+            used: ListEnds::new(),
+            free: ListEnds::new(),
+            size: 0,
+        }
+    }
+}
+
+impl<T> ForwardList<T> {
+    /// Creates a new empty forward list.
+    pub fn new() -> Self {
+        Default::default()
+    }
+    /// Creates a new empty forward list with room for at least `capacity`
+    /// elements.
+    pub fn with_capacity(capacity: usize) -> Self {
+        ForwardList::<T> {
+            elems: Vec::with_capacity(capacity),
+            next: Vec::with_capacity(capacity),
+            ..Default::default()
+        }
+    }
+    /// Returns the number of indexes, used or free.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.elems.len()
+    }
+    /// Returns the number of elements.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.size
+    }
+    /// Returns `true` when the list is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.used.is_empty()
+    }
+    /// Remove all the elements and all the indexes.
+    pub fn clear(&mut self) {
+        self.elems.clear();
+        self.next.clear();
+        self.used.clear();
+        self.free.clear();
+        self.size = 0;
+    }
+    /// Returns `true` if the index is valid.
+    #[inline]
+    pub fn is_index_used(&self, index: ListIndex) -> bool {
+        self.get(index).is_some()
+    }
+    /// Returns the index of the first element, or `None`.
+    #[inline]
+    pub fn first_index(&self) -> ListIndex {
+        self.used.head
+    }
+    /// Returns the index of the last element, or `None`.
+    #[inline]
+    pub fn last_index(&self) -> ListIndex {
+        self.used.tail
+    }
+    /// Returns the index of the element after the index, or `None` when the
+    /// index is last or not valid. The next index of `None` is the first
+    /// index.
+    #[inline]
+    pub fn next_index(&self, index: ListIndex) -> ListIndex {
+        match index.get() {
+            None => self.used.head,
+            Some(at) if self.is_index_used(index) => self.next[at],
+            Some(_) => ListIndex::new(),
+        }
+    }
+    /// Get a reference to the element data at the index, or `None`.
+    #[inline]
+    pub fn get(&self, index: ListIndex) -> Option<&T> {
+        self.elems.get(index.get()?)?.as_ref()
+    }
+    /// Get a mutable reference to the element data at the index, or `None`.
+    #[inline]
+    pub fn get_mut(&mut self, index: ListIndex) -> Option<&mut T> {
+        self.elems.get_mut(index.get()?)?.as_mut()
+    }
+    /// Get a reference to the first element data, or `None`.
+    #[inline]
+    pub fn get_first(&self) -> Option<&T> {
+        self.get(self.used.head)
+    }
+    /// Get a reference to the last element data, or `None`.
+    #[inline]
+    pub fn get_last(&self) -> Option<&T> {
+        self.get(self.used.tail)
+    }
+    /// Insert a new element at the beginning.
+    pub fn insert_first(&mut self, elem: T) -> ListIndex {
+        let this = self.new_node(elem);
+        self.set_next(this, self.used.head);
+        if self.used.is_empty() {
+            self.used.new_both(this);
+        } else {
+            self.used.new_head(this);
+        }
+        this
+    }
+    /// Insert a new element at the end.
+    pub fn insert_last(&mut self, elem: T) -> ListIndex {
+        let this = self.new_node(elem);
+        self.link_last(this);
+        this
+    }
+    /// Insert a new element after the index.
+    ///
+    /// If the index is `None`, or invalid, then the new element is inserted
+    /// last.
+    pub fn insert_after(&mut self, index: ListIndex, elem: T) -> ListIndex {
+        if !self.is_index_used(index) || index == self.used.tail {
+            return self.insert_last(elem);
+        }
+        let this = self.new_node(elem);
+        let next = self.next_index(index);
+        self.set_next(this, next);
+        self.set_next(index, this);
+        this
+    }
+    /// Remove the first element and return its data.
+    pub fn remove_first(&mut self) -> Option<T> {
+        let this = self.used.head;
+        let elem = self.take_elem(this)?;
+        let next = self.set_next(this, ListIndex::new());
+        self.used.new_head(next);
+        if next.is_none() {
+            self.used.clear();
+        }
+        self.link_free(this);
+        Some(elem)
+    }
+    /// Remove the element after the index and return its data, or `None` if
+    /// the index is not valid or is last. The element after `None` is the
+    /// first element.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::ForwardList;
+    /// let mut list: ForwardList<_> = (1..=3).collect();
+    /// let first = list.first_index();
+    /// assert_eq!(list.remove_after(first), Some(2));
+    /// assert_eq!(list.remove_after(list.last_index()), None);
+    /// assert_eq!(list.to_string(), "[1 > 3]");
+    /// ```
+    pub fn remove_after(&mut self, index: ListIndex) -> Option<T> {
+        if index.is_none() {
+            return self.remove_first();
+        }
+        let this = self.next_index(index);
+        let elem = self.take_elem(this)?;
+        let next = self.set_next(this, ListIndex::new());
+        self.set_next(index, next);
+        if next.is_none() {
+            self.used.new_tail(index);
+        }
+        self.link_free(this);
+        Some(elem)
+    }
+    /// Create a forward iterator over all the elements.
+    pub fn iter(&self) -> ForwardIter<'_, T> {
+        ForwardIter {
+            list: self,
+            next: self.used.head,
+            len: self.size,
+        }
+    }
+    /// Create an iterator that removes and returns the elements from the
+    /// front of the list.
+    ///
+    /// Any elements that were not drained are left in the list.
+    pub fn drain(&mut self) -> ForwardDrain<'_, T> {
+        ForwardDrain { list: self }
+    }
+    fn new_node(&mut self, elem: T) -> ListIndex {
+        self.size += 1;
+        let reuse = self.free.head;
+        if let Some(at) = reuse.get() {
+            let next = self.set_next(reuse, ListIndex::new());
+            self.free.new_head(next);
+            if next.is_none() {
+                self.free.clear();
+            }
+            self.elems[at] = Some(elem);
+            return reuse;
+        }
+        self.elems.push(Some(elem));
+        self.next.push(ListIndex::new());
+        ListIndex::from(self.elems.len() - 1)
+    }
+    fn take_elem(&mut self, this: ListIndex) -> Option<T> {
+        let elem = self.elems.get_mut(this.get()?)?.take()?;
+        self.size -= 1;
+        Some(elem)
+    }
+    #[inline]
+    fn set_next(&mut self, index: ListIndex, next: ListIndex) -> ListIndex {
+        match index.get() {
+            Some(at) => core::mem::replace(&mut self.next[at], next),
+            None => index,
+        }
+    }
+    fn link_last(&mut self, this: ListIndex) {
+        let prev = self.used.tail;
+        self.set_next(prev, this);
+        if self.used.is_empty() {
+            self.used.new_both(this);
+        } else {
+            self.used.new_tail(this);
+        }
+    }
+    fn link_free(&mut self, this: ListIndex) {
+        let prev = self.free.tail;
+        self.set_next(prev, this);
+        if self.free.is_empty() {
+            self.free.new_both(this);
+        } else {
+            self.free.new_tail(this);
+        }
+    }
+}
+
+impl<T> fmt::Display for ForwardList<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[")?;
+        for (n, elem) in self.iter().enumerate() {
+            if n > 0 {
+                write!(f, " > ")?;
+            }
+            write!(f, "{}", elem)?;
+        }
+        write!(f, "]")
+    }
+}
+
+impl<T> FromIterator<T> for ForwardList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = ForwardList::new();
+        iter.into_iter().for_each(|elem| { list.insert_last(elem); });
+        list
+    }
+}
+
+impl<T> Extend<T> for ForwardList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|elem| { self.insert_last(elem); });
+    }
+}
+
+/// A forward iterator over all the elements of a `ForwardList`.
+pub struct ForwardIter<'a, T> {
+    list: &'a ForwardList<T>,
+    next: ListIndex,
+    len: usize,
+}
+
+impl<'a, T> Iterator for ForwardIter<'a, T> {
+    type Item = &'a T;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let elem = self.list.get(self.next)?;
+        self.next = self.list.next_index(self.next);
+        self.len -= 1;
+        Some(elem)
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for ForwardIter<'_, T> {}
+
+impl<T> FusedIterator for ForwardIter<'_, T> {}
+
+/// A draining iterator that removes the elements from the front of a
+/// `ForwardList`.
+pub struct ForwardDrain<'a, T> {
+    list: &'a mut ForwardList<T>,
+}
+
+impl<T> Iterator for ForwardDrain<'_, T> {
+    type Item = T;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.remove_first()
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len(), Some(self.list.len()))
+    }
+}
+
+impl<T> ExactSizeIterator for ForwardDrain<'_, T> {}
+
+impl<T> FusedIterator for ForwardDrain<'_, T> {}
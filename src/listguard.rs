@@ -0,0 +1,68 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+//! The definition of the StructureGuard type
+use core::ops::Deref;
+use crate::{listindex::ListIndex, IndexList};
+
+/// A guard that freezes the structure of the list while it is held.
+///
+/// Nothing can be inserted, removed or moved through the guard, which is
+/// checked at compile time, so every index stays valid and keeps its place
+/// until the guard is dropped. The element data can still be changed.
+///
+/// The list can be read through `Deref`.
+///
+/// ```compile_fail
+/// # use index_list::IndexList;
+/// let mut list = IndexList::from(&mut vec![1, 2, 3]);
+/// let mut frozen = list.freeze_structure();
+/// frozen.insert_last(4);
+/// ```
+pub struct StructureGuard<'a, T>(&'a mut IndexList<T>);
+
+impl<'a, T> StructureGuard<'a, T> {
+    pub fn new(list: &'a mut IndexList<T>) -> Self {
+        StructureGuard::<'a, T>(list)
+    }
+    /// Get a mutable reference to the element data at the index, or `None`.
+    #[inline]
+    pub fn get_mut(&mut self, index: ListIndex) -> Option<&mut T> {
+        self.0.get_mut(index)
+    }
+    /// Get a mutable reference to the first element data, or `None`.
+    #[inline]
+    pub fn get_mut_first(&mut self) -> Option<&mut T> {
+        self.0.get_mut_first()
+    }
+    /// Get a mutable reference to the last element data, or `None`.
+    #[inline]
+    pub fn get_mut_last(&mut self) -> Option<&mut T> {
+        self.0.get_mut_last()
+    }
+    /// Update the element data at the index with a closure, see
+    /// `IndexList::update`.
+    #[inline]
+    pub fn update<R, F>(&mut self, index: ListIndex, f: F) -> Option<R>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        self.0.update(index, f)
+    }
+    /// Swap the element data between two indexes, see
+    /// `IndexList::swap_index`.
+    #[inline]
+    pub fn swap_index(&mut self, this: ListIndex, that: ListIndex) -> bool {
+        self.0.swap_index(this, that)
+    }
+}
+
+impl<T> Deref for StructureGuard<'_, T> {
+    type Target = IndexList<T>;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
@@ -4,37 +4,91 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 //! The defintions of the ListIter type
-use std::iter::{DoubleEndedIterator, FusedIterator};
+use core::iter::{DoubleEndedIterator, ExactSizeIterator, FusedIterator};
 use crate::{listindex::ListIndex, IndexList};
 
 /// A double-ended iterator over all the elements in the list. It is fused and
 /// can be reversed.
+///
+/// The iterator wraps around from the end of the list to the beginning, and
+/// the other way, which only matters when it does not start at an end, as
+/// with `IndexList::iter_rotated`.
 pub struct ListIter<'a, T> {
     pub(crate) list: &'a IndexList<T>,
     pub(crate) next: ListIndex,
     pub(crate) prev: ListIndex,
+    pub(crate) len: usize,
+}
+
+impl<T> ListIter<'_, T> {
+    /// Fast-forward the iterator so that the next element is the one at
+    /// `index`.
+    ///
+    /// Returns `true` if the iterator was repositioned. If `index` is not
+    /// among the elements that remain to be iterated then `false` is returned
+    /// and the iterator is left unchanged.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let list = IndexList::from(&mut vec![1, 2, 3, 4, 5]);
+    /// let index = list.move_index(list.first_index(), 3);
+    /// let mut iter = list.iter();
+    /// assert_eq!(iter.next(), Some(&1));
+    /// assert!(iter.skip_to(index));
+    /// assert_eq!(iter.next(), Some(&4));
+    /// assert!(!iter.skip_to(list.first_index()));
+    /// assert_eq!(iter.next(), Some(&5));
+    /// ```
+    pub fn skip_to(&mut self, index: ListIndex) -> bool {
+        let mut next = self.next;
+        for skipped in 0..self.len {
+            if next == index {
+                self.next = next;
+                self.len -= skipped;
+                return true;
+            }
+            next = self.list.next_index(next);
+        }
+        false
+    }
 }
 
 impl<'a, T> Iterator for ListIter<'a, T> {
     type Item = &'a T;
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        if self.next.is_none() {
+            self.next = self.list.first_index();
+        }
         let item = self.list.get(self.next);
         self.next = self.list.next_index(self.next);
+        self.len -= 1;
         item
     }
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let my_len = self.list.len();
-        (my_len, Some(my_len))
+        (self.len, Some(self.len))
     }
 }
 impl<T> FusedIterator for ListIter<'_, T> {}
 
+impl<T> ExactSizeIterator for ListIter<'_, T> {}
+
 impl<T> DoubleEndedIterator for ListIter<'_, T> {
     fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        if self.prev.is_none() {
+            self.prev = self.list.last_index();
+        }
         let item = self.list.get(self.prev);
         self.prev = self.list.prev_index(self.prev);
+        self.len -= 1;
         item
     }
 }
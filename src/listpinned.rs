@@ -0,0 +1,70 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+//! Address-stable storage, for lists of pinned boxes
+//!
+use core::pin::Pin;
+use alloc::boxed::Box;
+use crate::{listindex::ListIndex, IndexList};
+
+/// A list of pinned boxes keeps every element at the same address for as long
+/// as it is in the list, even when the vector grows or `trim_swap` moves the
+/// element to another index, since only the box pointer is ever moved.
+impl<T> IndexList<Pin<Box<T>>> {
+    /// Insert a new element, boxed and pinned, at the beginning.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// # use std::pin::Pin;
+    /// let mut list = IndexList::<Pin<Box<u64>>>::new();
+    /// let index = list.insert_first_pinned(42);
+    /// assert_eq!(list.get_pinned(index).map(|p| *p), Some(42));
+    /// ```
+    pub fn insert_first_pinned(&mut self, elem: T) -> ListIndex {
+        self.insert_first(Box::pin(elem))
+    }
+    /// Insert a new element, boxed and pinned, at the end.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// # use std::pin::Pin;
+    /// let mut list = IndexList::<Pin<Box<String>>>::new();
+    /// let index = list.insert_last_pinned("stable".to_string());
+    /// let addr: *const String = &*list.get_pinned(index).unwrap();
+    /// (0..100).for_each(|n| { list.insert_first_pinned(n.to_string()); });
+    /// list.remove_first();
+    /// list.trim_swap();
+    /// let index = list.last_index();
+    /// assert_eq!(&*list.get_pinned(index).unwrap() as *const String, addr);
+    /// ```
+    pub fn insert_last_pinned(&mut self, elem: T) -> ListIndex {
+        self.insert_last(Box::pin(elem))
+    }
+    /// Get a pinned reference to the element data at the index, or `None`.
+    #[inline]
+    pub fn get_pinned(&self, index: ListIndex) -> Option<Pin<&T>> {
+        self.get(index).map(|elem| elem.as_ref())
+    }
+    /// Get a pinned mutable reference to the element data at the index, or
+    /// `None`.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// # use std::pin::Pin;
+    /// let mut list = IndexList::<Pin<Box<u64>>>::new();
+    /// let index = list.insert_last_pinned(1);
+    /// if let Some(mut data) = list.get_pinned_mut(index) {
+    ///     *data += 1;
+    /// }
+    /// assert_eq!(list.get_pinned(index).map(|p| *p), Some(2));
+    /// ```
+    #[inline]
+    pub fn get_pinned_mut(&mut self, index: ListIndex) -> Option<Pin<&mut T>> {
+        self.get_mut(index).map(|elem| elem.as_mut())
+    }
+}
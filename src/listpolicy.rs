@@ -0,0 +1,51 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+//! The definition of the IndexPolicy type
+use core::{default::Default, fmt};
+use crate::listindex::ListIndex;
+
+/// Selects how a list treats an invalid index given to a mutating call that
+/// would otherwise fall back to a default behavior, such as `insert_before`
+/// inserting first.
+///
+/// A `None` index is not invalid, it stands for an end of the list. Calls
+/// that already report failure, by returning `false` or `None`, do so under
+/// every policy, and the `try_` variants, such as `try_insert_before`,
+/// return an error instead.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum IndexPolicy {
+    /// Silently fall back to the default behavior.
+    #[default]
+    Lenient,
+    /// Panic in debug builds, but fall back in release builds.
+    DebugPanic,
+    /// Always panic.
+    Panic,
+}
+
+impl IndexPolicy {
+    #[inline]
+    pub(crate) fn fallback(self, op: &str, index: ListIndex) {
+        match self {
+            IndexPolicy::Lenient => (),
+            IndexPolicy::DebugPanic => {
+                debug_assert!(false, "{}: invalid index {}", op, index);
+            }
+            IndexPolicy::Panic => panic!("{}: invalid index {}", op, index),
+        }
+    }
+}
+
+impl fmt::Display for IndexPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            IndexPolicy::Lenient => "lenient",
+            IndexPolicy::DebugPanic => "debug-panic",
+            IndexPolicy::Panic => "panic",
+        };
+        write!(f, "{}", name)
+    }
+}
@@ -0,0 +1,89 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+//! The definition of the Pool type
+use core::ops::Deref;
+use crate::{listindex::ListIndex, IndexList};
+
+/// An object pool with stable handles, where the objects can be walked in
+/// the order they were acquired.
+///
+/// A handle is the `ListIndex` of the object in the underlying list, and a
+/// released handle is reused, first in first out, by a later acquire, just
+/// as the list reuses its free indexes.
+///
+/// The live objects can be read through `Deref`, for example with `iter`.
+///
+/// Example:
+/// ```rust
+/// # use index_list::Pool;
+/// let mut bullets = Pool::<(i32, i32)>::new();
+/// let (first, pos) = bullets.acquire();
+/// *pos = (1, 2);
+/// let (second, _) = bullets.acquire_with(|| (5, 5));
+/// assert_eq!(bullets.release(first), Some((1, 2)));
+/// let (third, _) = bullets.acquire();
+/// assert_eq!(third, first);
+/// let live: Vec<_> = bullets.iter().collect();
+/// assert_eq!(live, vec![&(5, 5), &(0, 0)]);
+/// # assert!(bullets.get(second).is_some());
+/// ```
+#[derive(Debug, Default)]
+pub struct Pool<T> {
+    list: IndexList<T>,
+}
+
+impl<T> Pool<T> {
+    /// Creates a new empty pool.
+    pub fn new() -> Self {
+        Pool { list: IndexList::new() }
+    }
+    /// Creates a new empty pool with room for at least `capacity` objects.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Pool { list: IndexList::with_capacity(capacity) }
+    }
+    /// Acquire a new default object and return its handle together with a
+    /// mutable reference to it.
+    pub fn acquire(&mut self) -> (ListIndex, &mut T)
+    where
+        T: Default,
+    {
+        self.acquire_with(T::default)
+    }
+    /// Acquire a new object made by the closure and return its handle
+    /// together with a mutable reference to it.
+    pub fn acquire_with<F>(&mut self, f: F) -> (ListIndex, &mut T)
+    where
+        F: FnOnce() -> T,
+    {
+        let index = self.list.insert_last(f());
+        // the index was just inserted, so it is valid
+        (index, self.list.get_mut(index).unwrap())
+    }
+    /// Release the object with the handle and return it, or `None` if the
+    /// handle is not valid.
+    #[inline]
+    pub fn release(&mut self, index: ListIndex) -> Option<T> {
+        self.list.remove(index)
+    }
+    /// Get a mutable reference to the object with the handle, or `None`.
+    #[inline]
+    pub fn get_mut(&mut self, index: ListIndex) -> Option<&mut T> {
+        self.list.get_mut(index)
+    }
+    /// Returns the underlying list.
+    #[inline]
+    pub fn into_inner(self) -> IndexList<T> {
+        self.list
+    }
+}
+
+impl<T> Deref for Pool<T> {
+    type Target = IndexList<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.list
+    }
+}
@@ -0,0 +1,67 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+//! Random reordering and sampling of the list, behind the `rand` feature
+//!
+use rand::{seq::{index, SliceRandom}, Rng};
+use crate::{listindex::ListIndex, IndexList};
+
+impl<T> IndexList<T> {
+    /// Randomly permute the order of the list.
+    ///
+    /// Only the links are changed, so every index keeps pointing at the same
+    /// element data.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut (0..10).collect());
+    /// let index = list.first_index();
+    /// list.shuffle(&mut rand::thread_rng());
+    /// assert_eq!(list.get(index), Some(&0));
+    /// let mut elems: Vec<_> = list.iter().copied().collect();
+    /// elems.sort();
+    /// assert_eq!(elems, (0..10).collect::<Vec<_>>());
+    /// ```
+    pub fn shuffle<R>(&mut self, rng: &mut R)
+    where
+        R: Rng + ?Sized,
+    {
+        let mut order = self.indexes();
+        order.shuffle(rng);
+        for index in order {
+            self.relink_last(index);
+        }
+    }
+    /// Returns an iterator over `k` distinct elements, together with their
+    /// indexes, picked uniformly at random. All the elements are returned, in
+    /// random order, if the list holds no more than `k` of them.
+    ///
+    /// The used slots are found by scanning the vector rather than walking
+    /// the list, so the iterator does not follow the list order.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let list = IndexList::from(&mut (0..100).collect());
+    /// let picked: Vec<_> = list.sample_iter(&mut rand::thread_rng(), 5).collect();
+    /// assert_eq!(picked.len(), 5);
+    /// for (index, elem) in picked {
+    ///     assert_eq!(list.get(index), Some(elem));
+    /// }
+    /// ```
+    pub fn sample_iter<R>(&self, rng: &mut R, k: usize)
+        -> impl Iterator<Item = (ListIndex, &T)> + '_
+    where
+        R: Rng + ?Sized,
+    {
+        let slots = self.bits.slots(true, 0, self.capacity());
+        let picked = index::sample(rng, slots.len(), k.min(slots.len()));
+        picked.into_iter().filter_map(move |n| {
+            let index = ListIndex::from(slots[n]);
+            self.get(index).map(|elem| (index, elem))
+        })
+    }
+}
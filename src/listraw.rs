@@ -0,0 +1,169 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+//! The raw parts of a list, for taking it apart and putting it back together
+use alloc::{vec, vec::Vec};
+use crate::{listbitmap::ListBitmap, listends::ListEnds, listerror::ListError,
+    listfinger::ListFinger, listindex::ListIndex, listnode::ListNode,
+    listpolicy::IndexPolicy, listtags::ListTags, IndexList};
+
+/// The links of one slot in the list vector.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct RawNode {
+    pub next: ListIndex,
+    pub prev: ListIndex,
+}
+
+/// The first and last index of a chain of slots, either the used elements or
+/// the free slots.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct RawEnds {
+    pub head: ListIndex,
+    pub tail: ListIndex,
+}
+
+/// The raw parts of a list, as returned by `IndexList::into_raw_parts`: the
+/// element slots, the node links, the ends of the used and free chains and
+/// the number of elements.
+pub type RawParts<T> = (Vec<Option<T>>, Vec<RawNode>, RawEnds, RawEnds, usize);
+
+impl<T> IndexList<T> {
+    /// Take the list apart into its raw parts, see `RawParts`.
+    ///
+    /// The element tags and the index policy are not part of the raw parts.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 2, 3]);
+    /// list.remove_first();
+    /// let (elems, nodes, used, free, size) = list.into_raw_parts();
+    /// assert_eq!(elems, vec![None, Some(2), Some(3)]);
+    /// assert_eq!(nodes.len(), 3);
+    /// assert_eq!(size, 2);
+    /// assert_eq!(free.head, free.tail);
+    /// assert_ne!(used.head, used.tail);
+    /// ```
+    pub fn into_raw_parts(self) -> RawParts<T> {
+        let nodes = self.nodes
+            .iter()
+            .map(|node| RawNode { next: node.next, prev: node.prev })
+            .collect();
+        let used = RawEnds { head: self.used.head, tail: self.used.tail };
+        let free = RawEnds { head: self.free.head, tail: self.free.tail };
+        (self.elems, nodes, used, free, self.size)
+    }
+    /// Put a list back together from its raw parts, with every index
+    /// pointing to the same element as before.
+    ///
+    /// The parts are checked with `validate` and the first invariant that
+    /// does not hold is returned as the error.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::{IndexList, ListError};
+    /// let list = IndexList::from(&mut vec![1, 2, 3]);
+    /// let index = list.last_index();
+    /// let (elems, mut nodes, used, free, size) = list.into_raw_parts();
+    /// let parts = (elems.clone(), nodes.clone(), used, free, size);
+    /// let list = IndexList::from_raw_parts(parts).unwrap();
+    /// assert_eq!(list.get(index), Some(&3));
+    /// let parts = (elems.clone(), nodes.clone(), used, free, 2);
+    /// let error = IndexList::from_raw_parts(parts).unwrap_err();
+    /// assert_eq!(error, ListError::SizeMismatch { size: 2, elems: 3 });
+    /// nodes[2].next = nodes[1].prev;
+    /// let error = IndexList::from_raw_parts((elems, nodes, used, free, size)).unwrap_err();
+    /// assert!(matches!(error, ListError::Cycle(_)));
+    /// ```
+    pub fn from_raw_parts(parts: RawParts<T>) -> Result<IndexList<T>, ListError> {
+        let (elems, nodes, used, free, size) = parts;
+        if nodes.len() != elems.len() {
+            return Err(ListError::LengthMismatch { elems: elems.len(), nodes: nodes.len() });
+        }
+        if u32::try_from(elems.len()).is_err() {
+            return Err(ListError::TooLarge(elems.len()));
+        }
+        let mut bits = ListBitmap::with_capacity(elems.len());
+        elems.iter().for_each(|elem| bits.push(elem.is_some()));
+        let list = IndexList {
+            elems,
+            nodes: nodes
+                .iter()
+                .map(|node| ListNode { next: node.next, prev: node.prev })
+                .collect(),
+            used: ListEnds { head: used.head, tail: used.tail },
+            free: ListEnds { head: free.head, tail: free.tail },
+            size,
+            bits,
+            finger: ListFinger::new(),
+            tags: ListTags::new(),
+            policy: IndexPolicy::default(),
+            #[cfg(feature = "fail-fast")]
+            stamp: crate::listchecked::new_stamp(),
+        };
+        list.validate()?;
+        Ok(list)
+    }
+    /// Check that the internal structure of the list is consistent, and
+    /// return the first invariant that does not hold.
+    ///
+    /// Every slot with an element must be on the used chain and every empty
+    /// slot on the free chain, unless it has no links as after
+    /// `acquire_free_slot`, both chains must link each way and end at their
+    /// tails, and the length must be the number of elements. This is always
+    /// true for a list that was built through its methods.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::IndexList;
+    /// let mut list = IndexList::from(&mut vec![1, 2, 3]);
+    /// list.remove_first();
+    /// assert_eq!(list.validate(), Ok(()));
+    /// ```
+    pub fn validate(&self) -> Result<(), ListError> {
+        let mut visited = vec![false; self.capacity()];
+        self.walk_chain(&self.used, true, &mut visited)?;
+        self.walk_chain(&self.free, false, &mut visited)?;
+        let unlinked = (0..self.capacity()).find(|&at| !visited[at] && !self.is_detached(at));
+        if let Some(at) = unlinked {
+            return Err(ListError::Unlinked(ListIndex::from(at)));
+        }
+        let elems = self.elems.iter().filter(|elem| elem.is_some()).count();
+        if elems != self.size {
+            return Err(ListError::SizeMismatch { size: self.size, elems });
+        }
+        Ok(())
+    }
+    // walk the chain from head to tail, checking each node and marking it
+    // as visited
+    fn walk_chain(&self, ends: &ListEnds, used: bool, visited: &mut [bool])
+        -> Result<(), ListError>
+    {
+        let mut prev = ListIndex::new();
+        let mut index = ends.head;
+        while let Some(at) = index.get() {
+            if at >= self.capacity() {
+                return Err(ListError::OutOfRange(index));
+            }
+            if visited[at] {
+                return Err(ListError::Cycle(index));
+            }
+            if self.elems[at].is_some() != used {
+                return Err(ListError::WrongChain(index));
+            }
+            let node = self.get_indexnode(at).ok_or(ListError::OutOfRange(index))?;
+            if node.prev != prev {
+                return Err(ListError::BrokenLink(index));
+            }
+            visited[at] = true;
+            prev = index;
+            index = node.next;
+        }
+        if prev != ends.tail {
+            return Err(ListError::WrongTail { tail: ends.tail, last: prev });
+        }
+        Ok(())
+    }
+}
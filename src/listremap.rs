@@ -0,0 +1,50 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+//! The definition of the RemapTable type
+use alloc::{vec, vec::Vec};
+use crate::listindex::ListIndex;
+
+/// A mapping from the indexes of one list to the indexes that the same
+/// elements got in another list, for updating indexes that are stored
+/// elsewhere after the elements were copied or moved.
+#[derive(Clone, Debug, Default)]
+pub struct RemapTable {
+    map: Vec<ListIndex>,
+}
+
+impl RemapTable {
+    #[inline]
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        RemapTable {
+            map: vec![ListIndex::new(); capacity],
+        }
+    }
+    #[inline]
+    pub(crate) fn insert(&mut self, old: ListIndex, new: ListIndex) {
+        if let Some(at) = old.get() {
+            if at >= self.map.len() {
+                self.map.resize(at + 1, ListIndex::new());
+            }
+            self.map[at] = new;
+        }
+    }
+    /// Returns the new index for the old index, or `None` if the old index
+    /// was not mapped.
+    #[inline]
+    pub fn get(&self, old: ListIndex) -> ListIndex {
+        old.get()
+            .and_then(|at| self.map.get(at).copied())
+            .unwrap_or_default()
+    }
+    /// Returns an iterator over all the pairs of old and new indexes.
+    pub fn iter(&self) -> impl Iterator<Item = (ListIndex, ListIndex)> + '_ {
+        self.map
+            .iter()
+            .enumerate()
+            .filter(|(_, new)| new.is_some())
+            .map(|(at, &new)| (ListIndex::from(at), new))
+    }
+}
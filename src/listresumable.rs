@@ -0,0 +1,87 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+//! The definition of the ResumableIter type
+use crate::{listindex::ListIndex, IndexList};
+
+/// A forward iterator that does not borrow the list, so the list can be
+/// changed between the steps of a long scan.
+///
+/// The list is passed to each call instead, and before every step the
+/// iterator re-synchronizes with it. The next element is taken to be the one
+/// after the element it returned last, if that is still in the list, which
+/// picks up elements inserted right after it. Otherwise it goes on with the
+/// element it would have returned next, if that has not been removed.
+///
+/// Only the indexes are kept, so a removed element whose index is reused by
+/// a later insert can not be told apart from the original element.
+///
+/// Example:
+/// ```rust
+/// # use index_list::IndexList;
+/// let mut list = IndexList::from(&mut vec![1, 2, 3, 4]);
+/// let mut scan = list.resumable_iter();
+/// while let Some((index, &elem)) = scan.next(&list) {
+///     if elem % 2 == 0 {
+///         list.remove(index);
+///     } else if elem == 3 {
+///         list.insert_after(index, 5);
+///     }
+/// }
+/// assert_eq!(list.to_string(), "[1 >< 3 >< 5]");
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ResumableIter {
+    current: ListIndex,
+    next: ListIndex,
+}
+
+impl ResumableIter {
+    /// Creates a new iterator that starts at the first element of the list.
+    pub fn new<T>(list: &IndexList<T>) -> Self {
+        ResumableIter {
+            current: ListIndex::new(),
+            next: list.first_index(),
+        }
+    }
+    /// Returns the index of the element that was returned last, or `None`
+    /// if no element has been returned yet.
+    #[inline]
+    pub fn current_index(&self) -> ListIndex {
+        self.current
+    }
+    /// Returns the index of the element that will be returned next, as of the
+    /// last re-synchronization.
+    #[inline]
+    pub fn next_index(&self) -> ListIndex {
+        self.next
+    }
+    /// Re-synchronize with the list after it was changed.
+    ///
+    /// Returns `false` if both the element returned last and the one that was
+    /// next have been removed, in which case the iterator has lost its place
+    /// and is ended.
+    pub fn resync<T>(&mut self, list: &IndexList<T>) -> bool {
+        if list.is_index_used(self.current) {
+            self.next = list.next_index(self.current);
+        } else if !list.is_index_used(self.next) {
+            let lost = self.current.is_some() || self.next.is_some();
+            self.current = ListIndex::new();
+            self.next = ListIndex::new();
+            return !lost;
+        }
+        true
+    }
+    /// Re-synchronize with the list and then return the next element data
+    /// together with its index, or `None` when the end is reached.
+    pub fn next<'a, T>(&mut self, list: &'a IndexList<T>) -> Option<(ListIndex, &'a T)> {
+        self.resync(list);
+        let index = self.next;
+        let elem = list.get(index)?;
+        self.current = index;
+        self.next = list.next_index(index);
+        Some((index, elem))
+    }
+}
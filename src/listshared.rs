@@ -0,0 +1,90 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+//! The definition of the SharedIndexList type
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+use crate::IndexList;
+
+/// An index list that can be shared between threads, where readers get a
+/// snapshot of the list and writers are serialized.
+///
+/// A reader takes a snapshot with `load`, which only holds a lock long
+/// enough to clone an `Arc`, and can then read it for as long as it wants
+/// without blocking anyone. A writer changes a copy of the current list and
+/// then publishes it, so a write costs a clone of the whole list. This suits
+/// read-heavy data, such as caches, that changes rarely.
+///
+/// Example:
+/// ```rust
+/// # use index_list::{IndexList, SharedIndexList};
+/// # use std::{sync::Arc, thread};
+/// let shared = Arc::new(SharedIndexList::new(IndexList::from(&mut vec![1, 2])));
+/// let before = shared.load();
+/// let writer = Arc::clone(&shared);
+/// thread::spawn(move || writer.update(|list| list.insert_last(3)))
+///     .join()
+///     .unwrap();
+/// assert_eq!(before.to_string(), "[1 >< 2]");
+/// assert_eq!(shared.load().to_string(), "[1 >< 2 >< 3]");
+/// ```
+#[derive(Debug, Default)]
+pub struct SharedIndexList<T> {
+    current: RwLock<Arc<IndexList<T>>>,
+    writer: Mutex<()>,
+}
+
+impl<T> SharedIndexList<T> {
+    /// Creates a shared list, starting from the given list.
+    pub fn new(list: IndexList<T>) -> Self {
+        SharedIndexList {
+            current: RwLock::new(Arc::new(list)),
+            writer: Mutex::new(()),
+        }
+    }
+    /// Returns a snapshot of the current list, which is not affected by any
+    /// later writes.
+    pub fn load(&self) -> Arc<IndexList<T>> {
+        let current = self.current.read().unwrap_or_else(PoisonError::into_inner);
+        Arc::clone(&current)
+    }
+    /// Replace the current list and return the old one.
+    pub fn store(&self, list: IndexList<T>) -> Arc<IndexList<T>> {
+        let _writer = self.writer.lock().unwrap_or_else(PoisonError::into_inner);
+        self.publish(Arc::new(list))
+    }
+    // swap in the new list, holding the lock only for the swap
+    fn publish(&self, list: Arc<IndexList<T>>) -> Arc<IndexList<T>> {
+        let mut current = self.current.write().unwrap_or_else(PoisonError::into_inner);
+        std::mem::replace(&mut current, list)
+    }
+}
+
+impl<T> SharedIndexList<T>
+where
+    T: Clone,
+{
+    /// Change a copy of the current list with the closure and then publish
+    /// it, returning the result of the closure.
+    ///
+    /// Writers are serialized, so no update is lost, while readers keep
+    /// using the snapshots they have. If the closure panics nothing is
+    /// published.
+    pub fn update<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut IndexList<T>) -> R,
+    {
+        let _writer = self.writer.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut list = IndexList::clone(&self.load());
+        let result = f(&mut list);
+        self.publish(Arc::new(list));
+        result
+    }
+}
+
+impl<T> From<IndexList<T>> for SharedIndexList<T> {
+    fn from(list: IndexList<T>) -> Self {
+        SharedIndexList::new(list)
+    }
+}
@@ -0,0 +1,110 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+//! The definition of the StickyCursor type
+use crate::{listindex::ListIndex, IndexList};
+
+/// A cursor that stays on a valid element when the element under it is
+/// removed, whether through the cursor or directly from the list.
+///
+/// The cursor does not borrow the list, which is passed to each call
+/// instead. It remembers the neighbours of its element, as of the last call,
+/// and when the element is gone it moves to the next one, or the previous
+/// one at the end of the list. If those have been removed too, the cursor
+/// has lost its place and points to `None`.
+///
+/// Only the indexes are kept, so a removed element whose index is reused by
+/// a later insert can not be told apart from the original element.
+///
+/// Example:
+/// ```rust
+/// # use index_list::{IndexList, StickyCursor};
+/// let mut list = IndexList::from(&mut vec![1, 2, 3, 4]);
+/// let mut cursor = StickyCursor::new(&list, list.get_nth(1));
+/// list.remove(cursor.index(&list));
+/// assert_eq!(cursor.get(&list), Some(&3));
+/// assert_eq!(cursor.remove(&mut list), Some(3));
+/// assert_eq!(cursor.get(&list), Some(&4));
+/// cursor.remove(&mut list);
+/// assert_eq!(cursor.get(&list), Some(&1));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StickyCursor {
+    current: ListIndex,
+    next: ListIndex,
+    prev: ListIndex,
+}
+
+impl StickyCursor {
+    /// Creates a new cursor on the element at the index, or pointing to
+    /// `None` if the index is not valid.
+    pub fn new<T>(list: &IndexList<T>, index: ListIndex) -> Self {
+        let mut cursor = StickyCursor::default();
+        cursor.place(list, index);
+        cursor
+    }
+    /// Re-synchronize with the list and return the index of the element
+    /// under the cursor.
+    pub fn index<T>(&mut self, list: &IndexList<T>) -> ListIndex {
+        if !list.is_index_used(self.current) {
+            let index = if list.is_index_used(self.next) {
+                self.next
+            } else if self.next.is_none() && list.is_index_used(self.prev) {
+                self.prev
+            } else {
+                ListIndex::new()
+            };
+            self.place(list, index);
+        } else {
+            self.place(list, self.current);
+        }
+        self.current
+    }
+    /// Re-synchronize with the list and get a reference to the element data
+    /// under the cursor, or `None`.
+    pub fn get<'a, T>(&mut self, list: &'a IndexList<T>) -> Option<&'a T> {
+        let index = self.index(list);
+        list.get(index)
+    }
+    /// Re-synchronize with the list and get a mutable reference to the
+    /// element data under the cursor, or `None`.
+    pub fn get_mut<'a, T>(&mut self, list: &'a mut IndexList<T>) -> Option<&'a mut T> {
+        let index = self.index(list);
+        list.get_mut(index)
+    }
+    /// Move the cursor to the next element and return its index, which is
+    /// `None` at the end of the list.
+    pub fn move_next<T>(&mut self, list: &IndexList<T>) -> ListIndex {
+        let index = self.index(list);
+        self.place(list, list.next_index(index));
+        self.current
+    }
+    /// Move the cursor to the previous element and return its index, which
+    /// is `None` at the beginning of the list.
+    pub fn move_prev<T>(&mut self, list: &IndexList<T>) -> ListIndex {
+        let index = self.index(list);
+        self.place(list, list.prev_index(index));
+        self.current
+    }
+    /// Remove the element under the cursor and return its data. The cursor
+    /// moves to the next element, or to the previous one at the end of the
+    /// list.
+    pub fn remove<T>(&mut self, list: &mut IndexList<T>) -> Option<T> {
+        let index = self.index(list);
+        let elem = list.remove(index)?;
+        self.index(list);
+        Some(elem)
+    }
+    // put the cursor on the index and remember its neighbours
+    fn place<T>(&mut self, list: &IndexList<T>, index: ListIndex) {
+        if list.is_index_used(index) {
+            self.current = index;
+            self.next = list.next_index(index);
+            self.prev = list.prev_index(index);
+        } else {
+            *self = StickyCursor::default();
+        }
+    }
+}
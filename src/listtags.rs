@@ -0,0 +1,124 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+//! The definition of the ListTags type
+//!
+use core::default::Default;
+use alloc::vec::Vec;
+#[cfg(feature = "tags")]
+use core::mem;
+
+/// The tag of every slot, stored next to the links, where a missing tag is
+/// zero. The vector is only grown once a non-zero tag is set.
+///
+/// Without the `tags` feature nothing is stored and every tag is zero.
+#[derive(Clone, Debug, Default)]
+pub struct ListTags {
+    #[cfg(feature = "tags")]
+    tags: Vec<u32>,
+}
+
+#[cfg(feature = "tags")]
+impl ListTags {
+    #[allow(dead_code)]
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+    #[allow(dead_code)]
+    #[inline]
+    pub fn from_vec(tags: Vec<u32>) -> Self {
+        ListTags { tags }
+    }
+    #[inline]
+    pub fn get(&self, at: usize) -> u32 {
+        self.tags.get(at).copied().unwrap_or(0)
+    }
+    /// Set the tag of the slot, growing the vector to the capacity of the
+    /// list if needed, and return the old tag.
+    pub fn set(&mut self, at: usize, tag: u32, capacity: usize) -> u32 {
+        if self.tags.len() <= at {
+            if tag == 0 {
+                return 0;
+            }
+            self.tags.resize(capacity.max(at + 1), 0);
+        }
+        mem::replace(&mut self.tags[at], tag)
+    }
+    #[inline]
+    pub fn reset(&mut self, at: usize) {
+        if let Some(tag) = self.tags.get_mut(at) {
+            *tag = 0;
+        }
+    }
+    /// Give the tag of the source slot to the destination slot.
+    #[inline]
+    pub fn copy(&mut self, src: usize, dst: usize) {
+        let tag = self.get(src);
+        if let Some(old) = self.tags.get_mut(dst) {
+            *old = tag;
+        }
+    }
+    pub fn swap(&mut self, here: usize, there: usize) {
+        let high = here.max(there);
+        if self.tags.len() <= high {
+            if self.get(here) == self.get(there) {
+                return;
+            }
+            self.tags.resize(high + 1, 0);
+        }
+        self.tags.swap(here, there);
+    }
+    /// Add the tags of another list, whose slots start at `offset`.
+    pub fn append(&mut self, offset: usize, other: ListTags) {
+        if !other.tags.is_empty() {
+            self.tags.resize(offset, 0);
+            self.tags.extend(other.tags);
+        }
+    }
+    #[inline]
+    pub fn truncate(&mut self, len: usize) {
+        self.tags.truncate(len);
+    }
+    #[inline]
+    pub fn clear(&mut self) {
+        self.tags.clear();
+    }
+}
+
+#[cfg(not(feature = "tags"))]
+impl ListTags {
+    #[allow(dead_code)]
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+    #[allow(dead_code)]
+    #[inline]
+    pub fn from_vec(_tags: Vec<u32>) -> Self {
+        ListTags {}
+    }
+    #[inline]
+    pub fn get(&self, _at: usize) -> u32 {
+        0
+    }
+    #[allow(dead_code)]
+    #[inline]
+    pub fn set(&mut self, _at: usize, _tag: u32, _capacity: usize) -> u32 {
+        0
+    }
+    #[inline]
+    pub fn reset(&mut self, _at: usize) {}
+    #[inline]
+    pub fn copy(&mut self, _src: usize, _dst: usize) {}
+    #[inline]
+    pub fn swap(&mut self, _here: usize, _there: usize) {}
+    #[inline]
+    pub fn append(&mut self, _offset: usize, _other: ListTags) {}
+    #[inline]
+    pub fn truncate(&mut self, _len: usize) {}
+    #[inline]
+    pub fn clear(&mut self) {}
+}
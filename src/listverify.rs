@@ -0,0 +1,92 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+//! Kani proof harnesses for the link invariants, behind the `verification`
+//! feature
+//!
+//! Run them with `cargo kani --features verification`. Each harness drives
+//! a small list through every possible sequence of operations, up to a
+//! bound, and proves that `validate` holds after each step.
+//!
+use crate::{listindex::ListIndex, IndexList};
+
+const MAX_LEN: usize = 4;
+
+fn any_index(list: &IndexList<u8>) -> ListIndex {
+    let at: usize = kani::any();
+    kani::assume(at <= list.capacity());
+    if at == list.capacity() {
+        ListIndex::new()
+    } else {
+        ListIndex::from(at)
+    }
+}
+
+fn any_list() -> IndexList<u8> {
+    let mut list = IndexList::new();
+    let len: usize = kani::any();
+    kani::assume(len <= MAX_LEN);
+    for _ in 0..len {
+        if kani::any() {
+            list.insert_first(kani::any());
+        } else {
+            list.insert_last(kani::any());
+        }
+    }
+    let removed = any_index(&list);
+    list.remove(removed);
+    list
+}
+
+#[kani::proof]
+#[kani::unwind(6)]
+fn check_insert_remove() {
+    let mut list = any_list();
+    for _ in 0..2 {
+        let index = any_index(&list);
+        match kani::any::<u8>() % 4 {
+            0 => { list.insert_before(index, kani::any()); }
+            1 => { list.insert_after(index, kani::any()); }
+            2 => { list.remove(index); }
+            _ => { list.remove_first(); }
+        }
+        assert!(list.validate().is_ok());
+    }
+}
+
+#[kani::proof]
+#[kani::unwind(6)]
+fn check_shift() {
+    let mut list = any_list();
+    let len = list.len();
+    let this = any_index(&list);
+    let that = any_index(&list);
+    let elem = list.get(this).copied();
+    match kani::any::<u8>() % 5 {
+        0 => { list.shift_index_before(this, that); }
+        1 => { list.shift_index_after(this, that); }
+        2 => { list.shift_index_to_front(this); }
+        3 => { list.shift_index_to_back(this); }
+        _ => { list.touch(this); }
+    }
+    assert!(list.validate().is_ok());
+    assert_eq!(list.len(), len);
+    assert_eq!(list.get(this).copied(), elem);
+}
+
+#[kani::proof]
+#[kani::unwind(6)]
+fn check_trim() {
+    let mut list = any_list();
+    let elems: Vec<u8> = list.iter().copied().collect();
+    if kani::any() {
+        list.trim_swap();
+        assert_eq!(list.capacity(), list.len());
+    } else {
+        list.trim_safe();
+    }
+    assert!(list.validate().is_ok());
+    assert!(list.iter().copied().eq(elems));
+}
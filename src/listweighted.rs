@@ -0,0 +1,204 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+//! The definition of the WeightedList type
+use core::ops::Deref;
+use crate::{listindex::ListIndex, IndexList};
+
+/// An index list that keeps a running total of the weight of its elements.
+///
+/// The weight of an element is given by the weight function, and the total
+/// is updated on every insert, remove and update, so reading it is free.
+///
+/// The list can be read through `Deref`, while all changes go through the
+/// methods of the weighted list so that the total is always correct.
+///
+/// Example:
+/// ```rust
+/// # use index_list::WeightedList;
+/// let mut cache = WeightedList::new(|blob: &Vec<u8>| blob.len());
+/// let index = cache.insert_last(vec![0; 100]);
+/// cache.insert_last(vec![0; 50]);
+/// assert_eq!(cache.total_weight(), 150);
+/// cache.update(index, |blob| blob.truncate(10));
+/// assert_eq!(cache.total_weight(), 60);
+/// cache.remove_first();
+/// assert_eq!(cache.total_weight(), 50);
+/// ```
+pub struct WeightedList<T, W>
+where
+    W: Fn(&T) -> usize,
+{
+    list: IndexList<T>,
+    weigh: W,
+    total: usize,
+}
+
+impl<T, W> WeightedList<T, W>
+where
+    W: Fn(&T) -> usize,
+{
+    /// Creates a new empty list, where the weight of each element is given by
+    /// the `weigh` function.
+    pub fn new(weigh: W) -> Self {
+        WeightedList {
+            list: IndexList::new(),
+            weigh,
+            total: 0,
+        }
+    }
+    /// Creates a weighted list from an existing list, which is weighed once.
+    pub fn from_list(list: IndexList<T>, weigh: W) -> Self {
+        let total = list.iter().map(&weigh).sum();
+        WeightedList { list, weigh, total }
+    }
+    /// Returns the sum of the weights of all the elements.
+    #[inline]
+    pub fn total_weight(&self) -> usize {
+        self.total
+    }
+    /// Find the element that covers the offset, counting the weight of each
+    /// element as its length, and return its index together with the offset
+    /// within that element. Returns `None` when the offset is not less than
+    /// the total weight.
+    ///
+    /// The search walks from whichever end of the list is closer, going by
+    /// the total weight, so it is linear in the number of elements.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use index_list::WeightedList;
+    /// let mut rope = WeightedList::new(|piece: &&str| piece.len());
+    /// rope.insert_last("Hello");
+    /// let middle = rope.insert_last(", ");
+    /// let last = rope.insert_last("world");
+    /// assert_eq!(rope.find_by_offset(6), Some((middle, 1)));
+    /// assert_eq!(rope.find_by_offset(11), Some((last, 4)));
+    /// assert_eq!(rope.find_by_offset(12), None);
+    /// ```
+    pub fn find_by_offset(&self, offset: usize) -> Option<(ListIndex, usize)> {
+        if offset >= self.total {
+            return None;
+        }
+        if offset < self.total / 2 {
+            let mut start = 0;
+            let mut index = self.list.first_index();
+            while let Some(elem) = self.list.get(index) {
+                let end = start + (self.weigh)(elem);
+                if offset < end {
+                    return Some((index, offset - start));
+                }
+                start = end;
+                index = self.list.next_index(index);
+            }
+        } else {
+            let mut end = self.total;
+            let mut index = self.list.last_index();
+            while let Some(elem) = self.list.get(index) {
+                let start = end - (self.weigh)(elem);
+                if offset >= start {
+                    return Some((index, offset - start));
+                }
+                end = start;
+                index = self.list.prev_index(index);
+            }
+        }
+        None
+    }
+    /// Insert a new element at the beginning.
+    pub fn insert_first(&mut self, elem: T) -> ListIndex {
+        self.total += (self.weigh)(&elem);
+        self.list.insert_first(elem)
+    }
+    /// Insert a new element at the end.
+    pub fn insert_last(&mut self, elem: T) -> ListIndex {
+        self.total += (self.weigh)(&elem);
+        self.list.insert_last(elem)
+    }
+    /// Insert a new element before the index, see `IndexList::insert_before`.
+    pub fn insert_before(&mut self, index: ListIndex, elem: T) -> ListIndex {
+        self.total += (self.weigh)(&elem);
+        self.list.insert_before(index, elem)
+    }
+    /// Insert a new element after the index, see `IndexList::insert_after`.
+    pub fn insert_after(&mut self, index: ListIndex, elem: T) -> ListIndex {
+        self.total += (self.weigh)(&elem);
+        self.list.insert_after(index, elem)
+    }
+    /// Remove the element at the index and return its data.
+    pub fn remove(&mut self, index: ListIndex) -> Option<T> {
+        let elem = self.list.remove(index)?;
+        self.total -= (self.weigh)(&elem);
+        Some(elem)
+    }
+    /// Remove the first element and return its data.
+    #[inline]
+    pub fn remove_first(&mut self) -> Option<T> {
+        self.remove(self.list.first_index())
+    }
+    /// Remove the last element and return its data.
+    #[inline]
+    pub fn remove_last(&mut self) -> Option<T> {
+        self.remove(self.list.last_index())
+    }
+    /// Replace the element data at the index and return the old data, or
+    /// `None` if the index is not valid, in which case `elem` is dropped.
+    pub fn replace(&mut self, index: ListIndex, elem: T) -> Option<T> {
+        let new_weight = (self.weigh)(&elem);
+        let old = self.list.replace(index, elem)?;
+        self.total = self.total - (self.weigh)(&old) + new_weight;
+        Some(old)
+    }
+    /// Update the element data at the index with a closure and return its
+    /// result, or `None` if the index is not valid. The element is weighed
+    /// again afterwards.
+    pub fn update<R, F>(&mut self, index: ListIndex, f: F) -> Option<R>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        let weigh = &self.weigh;
+        let (result, before, after) = self.list.update(index, |data| {
+            let before = weigh(data);
+            let result = f(data);
+            (result, before, weigh(data))
+        })?;
+        self.total = self.total - before + after;
+        Some(result)
+    }
+    /// Make the element at the index come last in the list, see
+    /// `IndexList::touch`.
+    #[inline]
+    pub fn touch(&mut self, index: ListIndex) -> bool {
+        self.list.touch(index)
+    }
+    /// Make the element at the index come first in the list, see
+    /// `IndexList::shift_index_to_front`.
+    #[inline]
+    pub fn shift_index_to_front(&mut self, index: ListIndex) -> bool {
+        self.list.shift_index_to_front(index)
+    }
+    /// Make the element at the index come last in the list, see
+    /// `IndexList::shift_index_to_back`.
+    #[inline]
+    pub fn shift_index_to_back(&mut self, index: ListIndex) -> bool {
+        self.list.shift_index_to_back(index)
+    }
+    /// Returns the inner list.
+    #[inline]
+    pub fn into_inner(self) -> IndexList<T> {
+        self.list
+    }
+}
+
+impl<T, W> Deref for WeightedList<T, W>
+where
+    W: Fn(&T) -> usize,
+{
+    type Target = IndexList<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.list
+    }
+}
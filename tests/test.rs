@@ -3,7 +3,7 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
-use index_list::{IndexList, ListIndex};
+use index_list::{BoundedList, Evict, ForwardList, IndexList, IndexPolicy, ListIndex, StickyCursor};
 use std::mem::size_of;
 use std::collections::HashSet;
 use rand::{Rng, seq::SliceRandom};
@@ -34,7 +34,7 @@ fn test_instantiate() {
     assert_eq!(size_of::<ListIndex>(), 4);
     assert_eq!(list.len(), 0);
     assert_eq!(list.capacity(), 0);
-    assert_eq!(list.is_index_used(null), false);
+    assert!(!list.is_index_used(null));
     assert_eq!(list.first_index(), null);
     assert_eq!(list.last_index(), null);
     assert_eq!(list.next_index(null), null);
@@ -52,7 +52,7 @@ fn test_instantiate() {
     assert_eq!(list.remove_last(), None);
     assert_eq!(list.remove(null), None);
     assert_eq!(list.index_of(0), null);
-    assert_eq!(list.contains(0), false);
+    assert!(!list.contains(0));
     assert_eq!(list.to_vec(), Vec::<&u64>::new());
     let mut empty_list = IndexList::new();
     list.append(&mut empty_list);
@@ -67,7 +67,7 @@ fn basic_insert_remove() {
     let count = 9;
     (0..count).for_each(|i| {
         let ndx = list.insert_first(i);
-        assert_eq!(list.is_index_used(ndx), true);
+        assert!(list.is_index_used(ndx));
     });
     println!("{}", list);
     assert_eq!(list.capacity(), count as usize);
@@ -75,7 +75,7 @@ fn basic_insert_remove() {
     list.trim_swap();
     (0..count).rev().for_each(|i| {
         assert_eq!(list.remove_first(), Some(i));
-        assert_eq!(list.is_index_used(ListIndex::from(i as usize)), false);
+        assert!(!list.is_index_used(ListIndex::from(i as usize)));
         assert_eq!(list.len(), i as usize);
     });
     assert_eq!(list.remove_first(), None);
@@ -105,9 +105,9 @@ fn test_append() {
     assert_eq!(list.capacity(), 6);
     let index = list.move_index(list.first_index(), 3);
     assert_eq!(list.get(index), Some(&"D"));
-    list.get_mut(index).map(|chr| {
+    if let Some(chr) = list.get_mut(index) {
         *chr = "G";
-    });
+    }
     assert_eq!(list.get(index), Some(&"G"));
     let parts: Vec<&str> = list.iter().map(|e| e.as_ref()).collect();
     assert_eq!(parts.join(", "), "A, B, C, G, E, F");
@@ -156,7 +156,7 @@ fn test_single_element() {
         };
         assert_eq!(val, Some(num));
     }
-    assert_eq!(list.is_empty(), true);
+    assert!(list.is_empty());
     assert_eq!(list.capacity(), 1);
     assert_eq!(list.len(), 0);
 }
@@ -214,7 +214,7 @@ fn insert_remove_variants() {
         assert_eq!(list.len(), count);
         for c in (1..=count).rev() {
             let ndx = ListIndex::from(
-                indexes.swap_remove(rng.gen_range(0..c as usize)) - 1);
+                indexes.swap_remove(rng.gen_range(0..c)) - 1);
             println!("IndexList - remove {}", ndx);
             let num = list.remove(ndx).unwrap();
             //println!("IndexList: {}", list.to_debug_string());
@@ -228,3 +228,524 @@ fn insert_remove_variants() {
         assert_eq!(list.capacity(), 0);
     }
 }
+#[test]
+fn test_iter_both_ends() {
+    let list = IndexList::from(&mut vec![1, 2, 3, 4, 5]);
+    let mut iter = list.iter();
+    assert_eq!(iter.len(), 5);
+    assert_eq!(iter.next(), Some(&1));
+    assert_eq!(iter.next_back(), Some(&5));
+    assert_eq!(iter.next(), Some(&2));
+    assert_eq!(iter.next_back(), Some(&4));
+    assert_eq!(iter.len(), 1);
+    assert_eq!(iter.next(), Some(&3));
+    assert_eq!(iter.next_back(), None);
+    assert_eq!(iter.next(), None);
+    let (first, mut rest) = list.split_first().unwrap();
+    assert_eq!(*first, 1);
+    assert_eq!(rest.next_back(), Some(&5));
+    assert_eq!(rest.len(), 3);
+    assert_eq!(rest.rev().collect::<Vec<_>>(), vec![&4, &3, &2]);
+}
+#[test]
+fn test_drain_iter_len() {
+    let mut list = IndexList::from(&mut vec![1, 2, 3, 4]);
+    let mut drain = list.drain_iter();
+    assert_eq!(drain.len(), 4);
+    assert_eq!(drain.next(), Some(1));
+    assert_eq!(drain.next_back(), Some(4));
+    assert_eq!(drain.size_hint(), (2, Some(2)));
+    let rest: Vec<u64> = drain.collect();
+    assert_eq!(rest, vec![2, 3]);
+    assert!(list.is_empty());
+}
+#[test]
+fn test_drain_into_panic() {
+    struct Limited(Vec<u64>);
+    impl Extend<u64> for Limited {
+        fn extend<I: IntoIterator<Item = u64>>(&mut self, iter: I) {
+            for elem in iter {
+                assert!(self.0.len() < 2, "full");
+                self.0.push(elem);
+            }
+        }
+    }
+    let mut list = IndexList::from(&mut vec![1, 2, 3, 4]);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        list.drain_into(&mut Limited(Vec::new()));
+    }));
+    assert!(result.is_err());
+    assert_eq!(list.len(), 1);
+    assert_eq!(list.to_string(), "[4]");
+    assert!(list.validate().is_ok());
+}
+#[test]
+fn test_index_policy() {
+    let mut list = IndexList::from(&mut vec![1, 2]);
+    let stale = list.insert_last(3);
+    list.remove(stale);
+    let null = ListIndex::from(None);
+    list.insert_before(stale, 0);
+    list.insert_after(ListIndex::from(100usize), 4);
+    list.insert_after(null, 5);
+    assert_eq!(list.to_string(), "[0 >< 1 >< 2 >< 4 >< 5]");
+    list.set_index_policy(IndexPolicy::Panic);
+    assert!(list.shift_index_before(list.last_index(), null));
+    list.insert_after(list.last_index(), 6);
+    list.splice_before(null, [-1]);
+    assert_eq!(list.to_string(), "[-1 >< 5 >< 0 >< 1 >< 2 >< 4 >< 6]");
+    let bogus = ListIndex::from(100usize);
+    assert!(list.try_insert_before(bogus, 7).is_err());
+    let result = std::panic::catch_unwind(move || {
+        list.insert_before(bogus, 7);
+    });
+    assert!(result.is_err());
+}
+#[test]
+fn test_bounded_list() {
+    let mut evicted = Vec::new();
+    let mut list = BoundedList::new(3, Evict::Last, |index, elem| {
+        evicted.push((index, elem));
+    });
+    let one = list.insert_first(1u64);
+    list.insert_first(2);
+    list.insert_first(3);
+    list.insert_first(4);
+    assert_eq!(list.len(), 3);
+    assert!(!list.is_index_used(one));
+    list.set_max_len(1);
+    assert_eq!(list.to_string(), "[4]");
+    list.set_max_len(0);
+    assert!(list.is_empty());
+    let inner = list.into_inner();
+    assert!(inner.is_empty());
+    let values: Vec<u64> = evicted.iter().map(|(_, elem)| *elem).collect();
+    assert_eq!(values, vec![1, 2, 3, 4]);
+    assert_eq!(evicted[0].0, one);
+}
+#[test]
+fn test_positions() {
+    let mut rng = rand::thread_rng();
+    let mut list = IndexList::<u64>::new();
+    (0..64).for_each(|i| { list.insert_last(i); });
+    for _ in 0..256 {
+        let n = rng.gen_range(0..list.len());
+        let index = list.get_nth(n);
+        assert_eq!(list.position_of(index), Some(n));
+        let near = list.move_index_clamped(index, rng.gen_range(-3..=3));
+        let pos = list.iter().position(|e| Some(e) == list.get(near));
+        assert_eq!(list.position_of(near), pos);
+        if n % 4 == 0 {
+            let elem = list.remove(index).unwrap();
+            list.insert_first(elem);
+        }
+    }
+    assert_eq!(list.len(), 64);
+}
+#[cfg(feature = "tags")]
+#[test]
+fn test_tags_follow_elements() {
+    let mut list = IndexList::<u64>::new();
+    (0..8).for_each(|i| { list.insert_last(i); });
+    let mut index = list.first_index();
+    while index.is_some() {
+        let tag = *list.get(index).unwrap() as u32 * 10;
+        list.set_tag(index, tag);
+        index = list.next_index(index);
+    }
+    list.remove_first();
+    list.remove(list.get_nth(2));
+    let reused = list.insert_last(8);
+    assert_eq!(list.get_tag(reused), Some(0));
+    list.remove(reused);
+    list.trim_swap();
+    let mut index = list.first_index();
+    while index.is_some() {
+        assert_eq!(list.get_tag(index), Some(*list.get(index).unwrap() as u32 * 10));
+        index = list.next_index(index);
+    }
+    list.trim_safe();
+    assert_eq!(list.capacity(), 6);
+    let (first, second) = (list.first_index(), list.next_index(list.first_index()));
+    let tags = (list.get_tag(first), list.get_tag(second));
+    assert!(list.swap_index(first, second));
+    assert_eq!((list.get_tag(second), list.get_tag(first)), tags);
+    assert_eq!(list.get_tag(first), Some(*list.get(first).unwrap() as u32 * 10));
+}
+#[test]
+fn test_resort_index() {
+    let mut rng = rand::thread_rng();
+    let mut list = IndexList::<u64>::new();
+    (0..32).for_each(|i| { list.insert_last(i * 2); });
+    for _ in 0..256 {
+        let index = list.get_nth(rng.gen_range(0..list.len()));
+        list.update(index, |data| *data = rng.gen_range(0..80));
+        assert!(list.resort_index(index, |a, b| a.cmp(b)));
+        let sorted: Vec<_> = list.iter().collect();
+        assert!(sorted.windows(2).all(|w| w[0] <= w[1]));
+    }
+    assert!(!list.resort_index(ListIndex::new(), |a, b| a.cmp(b)));
+}
+#[test]
+fn test_sort_dedup() {
+    let mut rng = rand::thread_rng();
+    for len in [0, 1, 2, 3, 7, 64, 200] {
+        let mut list = IndexList::<u64>::new();
+        (0..len).for_each(|_| { list.insert_last(rng.gen_range(0..50)); });
+        let mut expected: Vec<_> = list.iter().copied().collect();
+        expected.sort();
+        expected.dedup();
+        let removed = list.sort_dedup_by(|a, b| a.cmp(b));
+        assert_eq!(removed, len - expected.len());
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), expected);
+        assert_eq!(list.iter().rev().count(), expected.len());
+        assert_eq!(list.capacity() - list.len(), removed);
+        assert_eq!(list.validate(), Ok(()));
+        (0..removed).for_each(|_| { list.insert_first(0); });
+        assert_eq!(list.capacity(), len);
+    }
+}
+
+#[test]
+fn test_free_slots() {
+    let mut list = IndexList::from(&mut vec![1, 2, 3, 4]);
+    let second = list.next_index(list.first_index());
+    list.remove(second);
+    let reused = list.acquire_free_slot();
+    assert_eq!(reused, second);
+    let grown = list.acquire_free_slot();
+    assert_eq!(grown, ListIndex::from(4usize));
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.capacity(), 5);
+    assert_eq!(list.validate(), Ok(()));
+    let fifth = list.insert_last(5);
+    assert_ne!(fifth, reused);
+    assert_ne!(fifth, grown);
+    assert!(!list.release_slot(fifth));
+    assert!(list.release_slot(grown));
+    assert!(!list.release_slot(grown));
+    assert_eq!(list.validate(), Ok(()));
+    list.trim_swap();
+    assert_eq!(list.capacity(), 5);
+    assert_eq!(list.validate(), Ok(()));
+    let last = list.acquire_free_slot();
+    list.trim_safe();
+    assert_eq!(list.capacity(), 6);
+    assert!(list.release_slot(last));
+    list.trim_safe();
+    assert_eq!(list.capacity(), 5);
+    assert!(list.release_slot(reused));
+    list.trim_swap();
+    assert_eq!(list.capacity(), 4);
+    assert_eq!(list.to_string(), "[1 >< 3 >< 4 >< 5]");
+    assert_eq!(list.validate(), Ok(()));
+    let slot = list.acquire_free_slot();
+    list.remove_first();
+    list.trim_swap();
+    assert_eq!(list.capacity(), 5);
+    assert_eq!(list.validate(), Ok(()));
+    assert_eq!(list.insert_first(1), ListIndex::from(0usize));
+    list.clear();
+    assert!(!list.release_slot(slot));
+}
+
+#[test]
+fn test_bogus_indexes() {
+    let mut list = IndexList::from(&mut vec![1, 2, 3, 4]);
+    let free = list.first_index();
+    list.remove(free);
+    for bogus in [ListIndex::new(), free, ListIndex::from(4usize), ListIndex::from(1000usize)] {
+        let used = list.last_index();
+        assert!(!list.is_index_used(bogus));
+        list.next_index(bogus);
+        list.prev_index(bogus);
+        list.move_index(bogus, 2);
+        list.move_index_checked(bogus, -2);
+        list.move_index_clamped(bogus, 2);
+        assert_eq!(list.position_of(bogus), None);
+        assert!(!list.shift_index_before(bogus, used));
+        assert!(!list.shift_index_after(bogus, used));
+        if bogus.is_some() {
+            assert!(!list.shift_index_before(used, bogus));
+            assert!(!list.shift_index_after(used, bogus));
+            assert!(list.try_insert_before(bogus, 0).is_err());
+            assert!(list.try_insert_after(bogus, 0).is_err());
+        }
+        assert!(!list.shift_index_to_front(bogus));
+        assert!(!list.shift_index_to_back(bogus));
+        assert!(!list.touch(bogus));
+        assert_eq!(list.update_and_touch(bogus, |_| ()), None);
+        assert!(!list.resort_index(bogus, |a, b| a.cmp(b)));
+        assert_eq!(list.get(bogus), None);
+        assert_eq!(list.get_mut(bogus), None);
+        #[cfg(feature = "tags")]
+        assert_eq!(list.get_tag(bogus), None);
+        #[cfg(feature = "tags")]
+        assert_eq!(list.set_tag(bogus, 1), None);
+        assert_eq!(list.update(bogus, |_| ()), None);
+        assert!(!list.swap_index(bogus, used));
+        assert!(!list.swap_index(used, bogus));
+        list.peek_next(bogus);
+        list.peek_prev(bogus);
+        assert_eq!(list.remove(bogus), None);
+        assert!(!list.release_slot(bogus));
+        list.iter_rotated(bogus).count();
+        assert_eq!(list.split(bogus).len(), 0);
+        list.as_cells().next_index(bogus);
+        list.as_cells().prev_index(bogus);
+        assert_eq!(list.as_cells().get(bogus), None);
+        assert!(!list.as_cells().set(bogus, 0));
+        let index = list.insert_before(bogus, 0);
+        list.remove(index);
+        let index = list.insert_after(bogus, 0);
+        list.remove(index);
+        assert!(!list.iter().skip_to(bogus));
+        assert_eq!(list.to_string(), "[2 >< 3 >< 4]");
+        assert_eq!(list.validate(), Ok(()));
+    }
+    let big = IndexList::from(&mut (0..100).collect());
+    let mut cursor = StickyCursor::new(&big, big.last_index());
+    let mut resume = big.resumable_iter();
+    resume.next(&big);
+    assert_eq!(cursor.get(&list), None);
+    assert_eq!(cursor.get_mut(&mut list), None);
+    cursor.move_next(&list);
+    cursor.move_prev(&list);
+    assert_eq!(cursor.remove(&mut list), None);
+    resume.resync(&list);
+    resume.next(&list);
+    assert_eq!(list.validate(), Ok(()));
+}
+
+#[test]
+fn test_forward_list() {
+    let mut rng = rand::thread_rng();
+    let mut list = ForwardList::new();
+    let mut expected = std::collections::VecDeque::new();
+    for n in 0..1000 {
+        match rng.gen_range(0..4) {
+            0 => { list.insert_first(n); expected.push_front(n); }
+            1 => { list.insert_last(n); expected.push_back(n); }
+            2 => assert_eq!(list.remove_first(), expected.pop_front()),
+            _ => {
+                let first = list.first_index();
+                assert_eq!(list.remove_after(first), expected.remove(1));
+            }
+        }
+        assert_eq!(list.len(), expected.len());
+        assert_eq!(list.get_last(), expected.back());
+    }
+    assert!(list.iter().eq(expected.iter()));
+    assert!(list.capacity() <= 1000);
+    assert!(list.drain().eq(expected.drain(..)));
+    assert_eq!(list.first_index(), ListIndex::new());
+    assert_eq!(list.last_index(), ListIndex::new());
+}
+
+#[test]
+fn test_cursor_mut_ghost() {
+    let mut list = IndexList::from(&mut vec![2, 3]);
+    let mut cursor = list.cursor_mut_back();
+    cursor.move_next();
+    assert_eq!(cursor.index(), ListIndex::new());
+    assert_eq!(cursor.current(), None);
+    assert_eq!(cursor.remove_current(), None);
+    cursor.insert_after(1);
+    cursor.insert_before(4);
+    assert_eq!(cursor.peek_next(), Some(&1));
+    assert_eq!(cursor.peek_prev(), Some(&4));
+    cursor.move_prev();
+    assert_eq!(cursor.remove_current(), Some(4));
+    assert_eq!(cursor.index(), ListIndex::new());
+    assert_eq!(list.to_string(), "[1 >< 2 >< 3]");
+}
+
+#[test]
+fn test_cursor_mut_splice() {
+    let mut list = IndexList::from(&mut vec![1, 9, 6]);
+    list.remove(list.next_index(list.first_index()));
+    let mut other = IndexList::from(&mut vec![0, 2, 3]);
+    let index = other.last_index();
+    other.remove_first();
+    let mut cursor = list.cursor_mut_front();
+    let remap = cursor.splice_after(other);
+    assert_eq!(cursor.current(), Some(&mut 1));
+    cursor.move_prev();
+    cursor.splice_after(IndexList::from(&mut vec![0]));
+    cursor.move_prev();
+    cursor.splice_before(IndexList::from(&mut vec![4, 5]));
+    cursor.splice_before(IndexList::new());
+    cursor.move_next();
+    cursor.splice_before(IndexList::from(&mut vec![7]));
+    assert_eq!(list.get(remap.get(index)), Some(&3));
+    assert_eq!(list.to_string(), "[0 >< 1 >< 2 >< 3 >< 4 >< 5 >< 6 >< 7]");
+    assert!(list.iter().rev().copied().eq((0..8).rev()));
+    assert_eq!(list.capacity() - list.len(), 2);
+}
+
+#[cfg(feature = "fail-fast")]
+#[test]
+fn test_append_keeps_stamp() {
+    let mut list = IndexList::from(&mut vec![0]);
+    let mut other = IndexList::from(&mut vec![1, 2]);
+    let stamp = other.structure_stamp();
+    let mut scan = other.iter_checked();
+    list.append(&mut other);
+    assert!(other.structure_stamp() > stamp);
+    for elem in 0..8 {
+        other.insert_last(elem);
+        assert_ne!(other.structure_stamp(), stamp);
+        assert_eq!(scan.next(&other), Err(index_list::ListError::StructureChanged));
+    }
+}
+
+#[cfg(feature = "fail-fast")]
+#[test]
+fn test_checked_iter() {
+    use index_list::ListError;
+    let changed = Err(ListError::StructureChanged);
+    let mut list = IndexList::from(&mut vec![1, 2, 3]);
+    let mut scan = list.iter_checked();
+    assert_eq!(scan.next(&list), Ok(Some((list.first_index(), &1))));
+    list.update(list.last_index(), |data| *data = 4);
+    assert_eq!(scan.next(&list).map(|found| found.map(|(_, elem)| *elem)), Ok(Some(2)));
+    list.insert_last(5);
+    assert_eq!(scan.next(&list), changed);
+    let mut scan = list.iter_checked();
+    list.remove_first();
+    assert_eq!(scan.next(&list), changed);
+    let mut scan = list.iter_checked();
+    list.shift_index_to_front(list.last_index());
+    assert_eq!(scan.next(&list), changed);
+    let mut scan = list.iter_checked();
+    list.append(&mut IndexList::from(&mut vec![6]));
+    assert_eq!(scan.next(&list), changed);
+    let mut scan = list.iter_checked();
+    list.append(&mut IndexList::new());
+    assert!(scan.next(&list).is_ok());
+    let copy = list.clone();
+    list = IndexList::new();
+    assert_eq!(scan.next(&list), changed);
+    let mut scan = list.iter_checked();
+    list = copy;
+    assert_eq!(scan.next(&list), changed);
+    let mut scan = list.iter_checked();
+    let found: Vec<_> = std::iter::from_fn(|| scan.next(&list).unwrap()).map(|(_, e)| *e).collect();
+    assert_eq!(found, vec![5, 2, 4, 6]);
+    assert_eq!(scan.next(&list), Ok(None));
+}
+
+#[test]
+fn test_split_keeps_indexes() {
+    let mut list = IndexList::from(&mut (0..10).collect());
+    let index = list.move_index(list.first_index(), 4);
+    list.remove(list.next_index(index));
+    list.shift_index_to_back(list.first_index());
+    #[cfg(feature = "tags")]
+    list.set_tag(list.last_index(), 7);
+    let mut moved = Vec::new();
+    let mut next = index;
+    while next.is_some() {
+        moved.push((next, *list.get(next).unwrap()));
+        next = list.next_index(next);
+    }
+    let other = list.split(index);
+    assert_eq!(other.len(), moved.len());
+    for (index, elem) in &moved {
+        assert_eq!(other.get(*index), Some(elem));
+        assert!(!list.is_index_used(*index));
+    }
+    assert_eq!(other.to_string(), "[4 >< 6 >< 7 >< 8 >< 9 >< 0]");
+    #[cfg(feature = "tags")]
+    assert_eq!(other.get_tag(other.last_index()), Some(7));
+    assert_eq!(list.to_string(), "[1 >< 2 >< 3]");
+    assert_eq!(other.validate(), Ok(()));
+    assert_eq!(list.validate(), Ok(()));
+}
+
+#[test]
+fn test_append_remap() {
+    let mut list = IndexList::from(&mut vec![1, 2, 3]);
+    list.remove_first();
+    let mut other = IndexList::from(&mut vec![4, 5, 6, 7]);
+    other.remove(other.next_index(other.first_index()));
+    #[cfg(feature = "tags")]
+    other.set_tag(other.last_index(), 9);
+    let moved = other.len();
+    let indexes: Vec<_> = (0..4usize).map(ListIndex::from).collect();
+    let remap = list.append(&mut other);
+    assert!(other.is_empty());
+    assert_eq!(other.validate(), Ok(()));
+    assert_eq!(list.validate(), Ok(()));
+    assert_eq!(list.capacity(), 7);
+    assert_eq!(list.to_string(), "[2 >< 3 >< 4 >< 6 >< 7]");
+    assert_eq!(remap.iter().count(), moved);
+    assert_eq!(remap.get(indexes[1]), ListIndex::new());
+    assert_eq!(list.get(remap.get(indexes[3])), Some(&7));
+    #[cfg(feature = "tags")]
+    assert_eq!(list.get_tag(remap.get(indexes[3])), Some(9));
+    (0..2).for_each(|n| { list.insert_last(n); });
+    assert_eq!(list.capacity(), 7);
+    assert_eq!(list.validate(), Ok(()));
+}
+
+#[test]
+fn test_rotate() {
+    for len in 0..8 {
+        for n in 0..2 * len + 1 {
+            let mut list: IndexList<_> = (0..len).collect();
+            let mut expected: Vec<_> = (0..len).collect();
+            list.rotate_left(n);
+            if len > 0 {
+                expected.rotate_left(n % len);
+            }
+            assert!(list.iter().eq(expected.iter()));
+            assert!(list.iter().rev().eq(expected.iter().rev()));
+            assert_eq!(list.validate(), Ok(()));
+            list.rotate_right(n);
+            assert!(list.iter().eq((0..len).collect::<Vec<_>>().iter()));
+        }
+    }
+}
+
+#[test]
+fn test_swap_positions() {
+    let len = 5;
+    for a in 0..len {
+        for b in 0..len {
+            let mut list: IndexList<_> = (0..len).collect();
+            let this = list.get_nth(a);
+            let that = list.get_nth(b);
+            assert!(list.swap_positions(this, that));
+            let mut expected: Vec<_> = (0..len).collect();
+            expected.swap(a, b);
+            assert!(list.iter().eq(expected.iter()));
+            assert_eq!(list.get(this), Some(&a));
+            assert_eq!(list.validate(), Ok(()));
+        }
+    }
+}
+
+#[test]
+fn test_sort() {
+    let mut rng = rand::thread_rng();
+    for len in [0, 1, 2, 3, 7, 64, 200] {
+        let mut list = IndexList::new();
+        (0..len).for_each(|n| { list.insert_last((rng.gen_range(0..20), n)); });
+        list.remove(list.get_nth(len / 2));
+        let mut expected: Vec<_> = list.iter().copied().collect();
+        expected.sort_by_key(|e| e.0);
+        let indexes: Vec<_> = expected.iter().map(|e| list.index_of(*e)).collect();
+        list.sort_by(|a, b| a.0.cmp(&b.0));
+        assert!(list.iter().eq(expected.iter()));
+        for (index, elem) in indexes.iter().zip(expected.iter()) {
+            assert_eq!(list.get(*index), Some(elem));
+        }
+        assert_eq!(list.validate(), Ok(()));
+        list.sort();
+        expected.sort();
+        assert!(list.iter().eq(expected.iter()));
+        assert!(list.iter().rev().eq(expected.iter().rev()));
+        assert_eq!(list.validate(), Ok(()));
+    }
+}